@@ -0,0 +1,614 @@
+//! Email address parsing and validation.
+//!
+//! Addresses are parsed following the `mailbox` and `addr-spec` grammar of
+//! [RFC 5322](https://tools.ietf.org/html/rfc5322#section-3.4). Display names
+//! may be plain words or quoted strings, and internationalized domains are
+//! converted to their ASCII (punycode) form. Comments and group syntax are not
+//! supported.
+//!
+//! ### Example
+//!
+//! ```
+//! # use mailgun_sdk::{Mailbox, MailboxList};
+//! let mailbox: Mailbox = "\"Doe, John\" <john@example.com>".parse().unwrap();
+//!
+//! assert_eq!(mailbox.name.as_deref(), Some("Doe, John"));
+//! assert_eq!(mailbox.address.to_string(), "john@example.com");
+//!
+//! let list: MailboxList = "bob@example.com, Alice <alice@bücher.example>".parse().unwrap();
+//!
+//! assert_eq!(list.to_string(), "bob@example.com, Alice <alice@xn--bcher-kva.example>");
+//! ```
+
+use crate::ParamError;
+use thiserror::Error;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Maximum length of the local part of an address.
+const MAX_LOCAL_PART_LENGTH: usize = 64;
+
+/// Maximum length of the domain of an address.
+const MAX_DOMAIN_LENGTH: usize = 255;
+
+/// Maximum length of a single domain label.
+const MAX_LABEL_LENGTH: usize = 63;
+
+/// Errors that might be encountered when parsing an address.
+#[derive(Debug, Error, PartialEq)]
+pub enum AddressError {
+    /// No address was given.
+    #[error("Address is empty")]
+    Empty,
+
+    /// The address has no `@` between the local part and the domain.
+    #[error("Address is missing an '@' separator")]
+    MissingAt,
+
+    /// The part before the `@` is not a valid dot-atom or quoted string.
+    #[error("Local part '{0}' is invalid")]
+    InvalidLocalPart(String),
+
+    /// The part after the `@` is not a valid domain or domain literal.
+    #[error("Domain '{0}' is invalid")]
+    InvalidDomain(String),
+
+    /// A quoted string was opened but never closed.
+    #[error("Quoted string is not terminated")]
+    UnterminatedQuote,
+
+    /// An address was opened with `<` but never closed with `>`.
+    #[error("Angle address is not terminated")]
+    UnterminatedAngleAddr,
+
+    /// A character appeared where it is not allowed.
+    #[error("Unexpected character '{0}' at position {1}")]
+    UnexpectedChar(char, usize),
+}
+
+//- Address
+
+/// A bare email address (`local-part@domain`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Address {
+    local_part: String,
+    domain: String,
+}
+
+impl Address {
+    /// The part of the address before the `@`, without any quoting.
+    pub fn local_part(&self) -> &str {
+        &self.local_part
+    }
+
+    /// The part of the address after the `@`, in its ASCII form.
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+}
+
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+
+        parser.skip_whitespace();
+
+        if parser.is_done() {
+            return Err(AddressError::Empty);
+        }
+
+        let address = parser.parse_addr_spec()?;
+
+        parser.expect_end()?;
+
+        Ok(address)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if is_dot_atom(&self.local_part) {
+            write!(f, "{}@{}", self.local_part, self.domain)
+        } else {
+            write!(f, "{}@{}", quote(&self.local_part), self.domain)
+        }
+    }
+}
+
+//- Mailbox
+
+/// An email address with an optional display name, such as
+/// `Bob <bob@host.com>`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Mailbox {
+    pub name: Option<String>,
+    pub address: Address,
+}
+
+impl Mailbox {
+    /// Create a new mailbox from a display name and an address.
+    pub fn new(name: Option<&str>, address: Address) -> Self {
+        Self {
+            name: name.map(|name| name.to_string()),
+            address,
+        }
+    }
+}
+
+impl From<Address> for Mailbox {
+    fn from(address: Address) -> Self {
+        Self {
+            name: None,
+            address,
+        }
+    }
+}
+
+impl FromStr for Mailbox {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+
+        parser.skip_whitespace();
+
+        if parser.is_done() {
+            return Err(AddressError::Empty);
+        }
+
+        let mailbox = parser.parse_mailbox()?;
+
+        parser.expect_end()?;
+
+        Ok(mailbox)
+    }
+}
+
+impl fmt::Display for Mailbox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.name {
+            Some(name) if is_phrase(name) => write!(f, "{} <{}>", name, self.address),
+            Some(name) => write!(f, "{} <{}>", quote(name), self.address),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+//- MailboxList
+
+/// A list of mailboxes, rendered in the comma-separated form used by the
+/// `to`, `cc` and `bcc` parameters.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MailboxList(pub Vec<Mailbox>);
+
+impl MailboxList {
+    /// Add a mailbox to the end of the list.
+    pub fn push(&mut self, mailbox: Mailbox) {
+        self.0.push(mailbox);
+    }
+
+    /// Iterate over the mailboxes in the list.
+    pub fn iter(&self) -> std::slice::Iter<'_, Mailbox> {
+        self.0.iter()
+    }
+
+    /// Number of mailboxes in the list.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the list contains no mailboxes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<Mailbox>> for MailboxList {
+    fn from(mailboxes: Vec<Mailbox>) -> Self {
+        Self(mailboxes)
+    }
+}
+
+impl FromStr for MailboxList {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let mut mailboxes = vec![];
+
+        loop {
+            parser.skip_whitespace();
+
+            if parser.is_done() {
+                break;
+            }
+
+            // Tolerate empty entries, such as a trailing comma.
+            if parser.eat(',') {
+                continue;
+            }
+
+            mailboxes.push(parser.parse_mailbox()?);
+
+            parser.skip_whitespace();
+
+            if !parser.is_done() && !parser.eat(',') {
+                return Err(parser.unexpected());
+            }
+        }
+
+        if mailboxes.is_empty() {
+            return Err(AddressError::Empty);
+        }
+
+        Ok(Self(mailboxes))
+    }
+}
+
+impl fmt::Display for MailboxList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, mailbox) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}", mailbox)?;
+        }
+
+        Ok(())
+    }
+}
+
+//- Parser
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(input: &str) -> Self {
+        Self {
+            chars: input.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn unexpected(&self) -> AddressError {
+        match self.peek() {
+            Some(c) => AddressError::UnexpectedChar(c, self.pos),
+            None => AddressError::Empty,
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<(), AddressError> {
+        self.skip_whitespace();
+
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    /// Parse either `display-name <addr-spec>`, `<addr-spec>` or a bare
+    /// `addr-spec`.
+    fn parse_mailbox(&mut self) -> Result<Mailbox, AddressError> {
+        let start = self.pos;
+        let name = self.parse_phrase()?;
+
+        self.skip_whitespace();
+
+        if self.eat('<') {
+            self.skip_whitespace();
+            let address = self.parse_addr_spec()?;
+            self.skip_whitespace();
+
+            if !self.eat('>') {
+                return Err(AddressError::UnterminatedAngleAddr);
+            }
+
+            return Ok(Mailbox { name, address });
+        }
+
+        // There was no angle address, so the words we read must have been the
+        // start of a bare address.
+        self.pos = start;
+
+        Ok(Mailbox::from(self.parse_addr_spec()?))
+    }
+
+    /// Parse a sequence of atoms and quoted strings, joined by single spaces.
+    fn parse_phrase(&mut self) -> Result<Option<String>, AddressError> {
+        let mut words = vec![];
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some('"') => words.push(self.parse_quoted_string()?),
+                // Periods are allowed in unquoted display names (obs-phrase).
+                Some(c) if is_atext(c) || c == '.' => {
+                    let mut word = String::new();
+
+                    while let Some(c) = self.peek().filter(|c| is_atext(*c) || *c == '.') {
+                        word.push(c);
+                        self.pos += 1;
+                    }
+
+                    words.push(word);
+                },
+                _ => break,
+            }
+        }
+
+        if words.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(words.join(" ")))
+        }
+    }
+
+    fn parse_quoted_string(&mut self) -> Result<String, AddressError> {
+        let mut value = String::new();
+
+        self.eat('"');
+
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(value);
+                },
+                Some('\\') => {
+                    self.pos += 1;
+
+                    match self.peek() {
+                        Some(c) => value.push(c),
+                        None => return Err(AddressError::UnterminatedQuote),
+                    }
+                },
+                Some(c) => value.push(c),
+                None => return Err(AddressError::UnterminatedQuote),
+            }
+
+            self.pos += 1;
+        }
+    }
+
+    fn parse_addr_spec(&mut self) -> Result<Address, AddressError> {
+        let local_part = if self.peek() == Some('"') {
+            self.parse_quoted_string()?
+        } else {
+            let mut local_part = String::new();
+
+            while let Some(c) = self.peek().filter(|c| is_atext(*c) || *c == '.') {
+                local_part.push(c);
+                self.pos += 1;
+            }
+
+            if !is_dot_atom(&local_part) {
+                if local_part.is_empty() && !self.is_done() && self.peek() != Some('@') {
+                    return Err(self.unexpected());
+                }
+
+                return Err(AddressError::InvalidLocalPart(local_part));
+            }
+
+            local_part
+        };
+
+        if local_part.is_empty() || local_part.len() > MAX_LOCAL_PART_LENGTH {
+            return Err(AddressError::InvalidLocalPart(local_part));
+        }
+
+        if !self.eat('@') {
+            return Err(AddressError::MissingAt);
+        }
+
+        let domain = if self.peek() == Some('[') {
+            self.parse_domain_literal()?
+        } else {
+            let mut domain = String::new();
+
+            while let Some(c) = self.peek().filter(|c| !c.is_whitespace() && !"<>,;\"()[]".contains(*c)) {
+                domain.push(c);
+                self.pos += 1;
+            }
+
+            normalize_domain(&domain)?
+        };
+
+        Ok(Address { local_part, domain })
+    }
+
+    fn parse_domain_literal(&mut self) -> Result<String, AddressError> {
+        let mut literal = String::new();
+
+        while let Some(c) = self.peek() {
+            literal.push(c);
+            self.pos += 1;
+
+            if c == ']' {
+                let inner = &literal[1..literal.len() - 1];
+
+                if inner.is_empty() || inner.contains(|c: char| c == '[' || c == '\\' || !c.is_ascii_graphic()) {
+                    return Err(AddressError::InvalidDomain(literal));
+                }
+
+                return Ok(literal);
+            }
+        }
+
+        Err(AddressError::InvalidDomain(literal))
+    }
+}
+
+//- Helpers
+
+/// Whether `c` is allowed in an unquoted atom. Non-ASCII characters are
+/// accepted as described in RFC 6532.
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c) || !c.is_ascii()
+}
+
+fn is_dot_atom(value: &str) -> bool {
+    !value.is_empty() && value.split('.').all(|atom| !atom.is_empty() && atom.chars().all(is_atext))
+}
+
+fn is_phrase(value: &str) -> bool {
+    value.split(' ').all(|word| !word.is_empty() && word.chars().all(is_atext))
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+
+    quoted.push('"');
+
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+
+        quoted.push(c);
+    }
+
+    quoted.push('"');
+
+    quoted
+}
+
+/// Convert a domain to its ASCII form and check each of its labels.
+fn normalize_domain(domain: &str) -> Result<String, AddressError> {
+    let invalid = || AddressError::InvalidDomain(domain.to_string());
+
+    let ascii = idna::domain_to_ascii(domain).map_err(|_| invalid())?;
+
+    if ascii.is_empty() || ascii.len() > MAX_DOMAIN_LENGTH {
+        return Err(invalid());
+    }
+
+    for label in ascii.split('.') {
+        let valid = !label.is_empty()
+            && label.len() <= MAX_LABEL_LENGTH
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+        if !valid {
+            return Err(invalid());
+        }
+    }
+
+    Ok(ascii)
+}
+
+/// Parse a bare address parameter and render it in its normalized form.
+pub(crate) fn normalize_address(key: &str, value: &str) -> Result<String, ParamError> {
+    value.parse::<Address>()
+        .map(|address| address.to_string())
+        .map_err(|error| ParamError::InvalidAddress(key.to_string(), error))
+}
+
+/// Parse a single address parameter and render it in its normalized form.
+pub(crate) fn normalize_mailbox(key: &str, value: &str) -> Result<String, ParamError> {
+    value.parse::<Mailbox>()
+        .map(|mailbox| mailbox.to_string())
+        .map_err(|error| ParamError::InvalidAddress(key.to_string(), error))
+}
+
+/// Parse a comma-separated address parameter and render it in its normalized
+/// form.
+pub(crate) fn normalize_mailbox_list(key: &str, value: &str) -> Result<String, ParamError> {
+    value.parse::<MailboxList>()
+        .map(|list| list.to_string())
+        .map_err(|error| ParamError::InvalidAddress(key.to_string(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bare_address() {
+        let mailbox: Mailbox = "bob@host.com".parse().unwrap();
+
+        assert_eq!(mailbox.name, None);
+        assert_eq!(mailbox.address.local_part(), "bob");
+        assert_eq!(mailbox.address.domain(), "host.com");
+    }
+
+    #[test]
+    fn parse_display_name() {
+        let mailbox: Mailbox = "Bob Smith <bob@host.com>".parse().unwrap();
+        assert_eq!(mailbox.name.as_deref(), Some("Bob Smith"));
+        assert_eq!(mailbox.to_string(), "Bob Smith <bob@host.com>");
+
+        let mailbox: Mailbox = r#""Smith, \"Bob\"" <bob@host.com>"#.parse().unwrap();
+        assert_eq!(mailbox.name.as_deref(), Some(r#"Smith, "Bob""#));
+        assert_eq!(mailbox.to_string(), r#""Smith, \"Bob\"" <bob@host.com>"#);
+
+        let mailbox: Mailbox = "<bob@host.com>".parse().unwrap();
+        assert_eq!(mailbox.name, None);
+    }
+
+    #[test]
+    fn parse_quoted_local_part() {
+        let address: Address = r#""bob smith"@host.com"#.parse().unwrap();
+
+        assert_eq!(address.local_part(), "bob smith");
+        assert_eq!(address.to_string(), r#""bob smith"@host.com"#);
+    }
+
+    #[test]
+    fn parse_idn_domain() {
+        let address: Address = "user@bücher.example".parse().unwrap();
+
+        assert_eq!(address.domain(), "xn--bcher-kva.example");
+    }
+
+    #[test]
+    fn parse_list() {
+        let list: MailboxList = r#"a@host.com, "Doe, Jane" <jane@host.com>,"#.parse().unwrap();
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.to_string(), r#"a@host.com, "Doe, Jane" <jane@host.com>"#);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!("".parse::<Mailbox>(), Err(AddressError::Empty));
+        assert_eq!("bob".parse::<Mailbox>(), Err(AddressError::MissingAt));
+        assert_eq!("bob..smith@host.com".parse::<Address>(), Err(AddressError::InvalidLocalPart("bob..smith".to_string())));
+        assert_eq!("bob@-host.com".parse::<Address>(), Err(AddressError::InvalidDomain("-host.com".to_string())));
+        assert_eq!("Bob <bob@host.com".parse::<Mailbox>(), Err(AddressError::UnterminatedAngleAddr));
+        assert_eq!("\"Bob <bob@host.com>".parse::<Mailbox>(), Err(AddressError::UnterminatedQuote));
+        assert!("a@host.com b@host.com".parse::<MailboxList>().is_err());
+    }
+}
@@ -0,0 +1,388 @@
+//! Time series analysis of stats returned by `get_stats`.
+//!
+//! These helpers turn a [`GetStatsResponse`](../get_stats/struct.GetStatsResponse.html)
+//! into a series of values per time bucket, compute common rates, compare
+//! consecutive periods, and group hourly or daily stats into coarser buckets.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     ParamList,
+//! #     analytics::Metric,
+//! #     get_stats::{GetStatsParam, GetStatsParamList, Resolution, StatEvent},
+//! # };
+//! # let client = Client::new("", "");
+//! let request = GetStatsParamList::default()
+//!     .add(GetStatsParam::Event(StatEvent::Complained));
+//!
+//! let stats = client.get_stats(request).unwrap();
+//!
+//! let delivered = stats.series(Metric::Delivered).unwrap();
+//! let delivery_rate = stats.delivery_rate().unwrap();
+//! let complaint_rate = stats.complaint_rate().unwrap();
+//!
+//! // Change in deliveries from one day to the next.
+//! let deltas = delivered.deltas();
+//!
+//! // Daily stats grouped by month.
+//! let monthly = stats.rebucket(Resolution::Month).unwrap();
+//! ```
+
+use crate::get_stats::{
+    self,
+    GetStatsResponse,
+    Resolution,
+    StatAccepted,
+    StatClicked,
+    StatDelivered,
+    StatFailed,
+    StatItem,
+    StatOpened,
+    StatTotal,
+};
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use thiserror::Error;
+
+use std::collections::BTreeMap;
+
+/// Errors that might be encountered when analyzing stats.
+#[derive(Debug, Error, PartialEq)]
+pub enum AnalyticsError {
+    /// A stat item's time is not in the RFC 2822 format used by the API.
+    #[error("Stat time '{0}' could not be parsed")]
+    InvalidTime(String),
+
+    /// The response's resolution is not one of hour, day or month.
+    #[error("Unknown resolution '{0}'")]
+    UnknownResolution(String),
+
+    /// Stats can only be grouped into buckets at least as large as the
+    /// response's resolution.
+    #[error("Cannot rebucket {from} stats into {to} buckets")]
+    FinerResolution { from: Resolution, to: Resolution },
+}
+
+/// A single counter found in [`StatItem`](../get_stats/struct.StatItem.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Accepted,
+    Delivered,
+    /// Both permanent and temporary failures.
+    Failed,
+    PermanentFailed,
+    TemporaryFailed,
+    /// Permanent failures caused by a bounce, including delayed bounces.
+    Bounced,
+    Opened,
+    UniqueOpened,
+    Clicked,
+    UniqueClicked,
+    Unsubscribed,
+    Complained,
+    Stored,
+}
+
+impl Metric {
+    /// Read this metric from a stat item. Returns `None` if the event was not
+    /// requested.
+    pub fn value(&self, item: &StatItem) -> Option<i64> {
+        match self {
+            Self::Accepted => item.accepted.as_ref().map(|v| v.total),
+            Self::Delivered => item.delivered.as_ref().map(|v| v.total),
+            Self::Failed => item.failed.as_ref().map(|v| v.permanent.total + v.temporary.espblock),
+            Self::PermanentFailed => item.failed.as_ref().map(|v| v.permanent.total),
+            Self::TemporaryFailed => item.failed.as_ref().map(|v| v.temporary.espblock),
+            Self::Bounced => item.failed.as_ref().map(|v| v.permanent.bounce + v.permanent.delayed_bounce),
+            Self::Opened => item.opened.as_ref().map(|v| v.total),
+            Self::UniqueOpened => item.opened.as_ref().and_then(|v| v.unique),
+            Self::Clicked => item.clicked.as_ref().map(|v| v.total),
+            Self::UniqueClicked => item.clicked.as_ref().and_then(|v| v.unique),
+            Self::Unsubscribed => item.unsubscribed.as_ref().map(|v| v.total),
+            Self::Complained => item.complained.as_ref().map(|v| v.total),
+            Self::Stored => item.stored.as_ref().map(|v| v.total),
+        }
+    }
+}
+
+/// A single value of a [`TimeSeries`](struct.TimeSeries.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Point {
+    /// Start of the time bucket.
+    pub time: DateTime<Utc>,
+    /// `None` when the value is unknown, such as a rate with nothing to divide
+    /// by.
+    pub value: Option<f64>,
+}
+
+/// Values of a metric or rate over time, in the order returned by the API.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TimeSeries {
+    pub points: Vec<Point>,
+}
+
+impl TimeSeries {
+    /// Sum of all known values.
+    pub fn total(&self) -> f64 {
+        self.points.iter().filter_map(|point| point.value).sum()
+    }
+
+    /// Difference between each point and the one before it. The result has
+    /// one point fewer than this series.
+    pub fn deltas(&self) -> TimeSeries {
+        let points = self.points
+            .windows(2)
+            .map(|pair| Point {
+                time: pair[1].time,
+                value: match (pair[0].value, pair[1].value) {
+                    (Some(previous), Some(current)) => Some(current - previous),
+                    _ => None,
+                },
+            })
+            .collect();
+
+        TimeSeries { points }
+    }
+}
+
+impl GetStatsResponse {
+    /// Values of a single metric over time.
+    pub fn series(&self, metric: Metric) -> Result<TimeSeries, AnalyticsError> {
+        self.ratio_series(|item| metric.value(item).map(|value| value as f64))
+    }
+
+    /// Percentage of accepted messages that were delivered.
+    pub fn delivery_rate(&self) -> Result<TimeSeries, AnalyticsError> {
+        self.rate(Metric::Delivered, Metric::Accepted)
+    }
+
+    /// Percentage of accepted messages that bounced.
+    pub fn bounce_rate(&self) -> Result<TimeSeries, AnalyticsError> {
+        self.rate(Metric::Bounced, Metric::Accepted)
+    }
+
+    /// Percentage of delivered messages that were marked as spam.
+    pub fn complaint_rate(&self) -> Result<TimeSeries, AnalyticsError> {
+        self.rate(Metric::Complained, Metric::Delivered)
+    }
+
+    /// Group the stats into larger time buckets, summing every counter.
+    ///
+    /// For example, hourly stats may be grouped by day or month, and daily
+    /// stats by month.
+    ///
+    /// Unique open and click counts cannot be summed, as a recipient may be
+    /// counted in several of the smaller buckets. They are set to `None` when
+    /// grouping into larger buckets.
+    pub fn rebucket(&self, resolution: Resolution) -> Result<GetStatsResponse, AnalyticsError> {
+        let from: Resolution = self.resolution
+            .parse()
+            .map_err(|_| AnalyticsError::UnknownResolution(self.resolution.clone()))?;
+
+        if resolution < from {
+            return Err(AnalyticsError::FinerResolution { from, to: resolution });
+        }
+
+        let mut buckets: BTreeMap<DateTime<Utc>, StatItem> = BTreeMap::new();
+
+        for item in self.stats.iter() {
+            let time = bucket_start(parse_time(&item.time)?, resolution);
+
+            match buckets.get_mut(&time) {
+                Some(bucket) => merge_item(bucket, item),
+                None => {
+                    let mut bucket = item.clone();
+                    bucket.time = format_time(time);
+
+                    if resolution > from {
+                        clear_unique(&mut bucket);
+                    }
+
+                    buckets.insert(time, bucket);
+                },
+            }
+        }
+
+        Ok(GetStatsResponse {
+            end: self.end.clone(),
+            resolution: resolution.to_string(),
+            start: self.start.clone(),
+            stats: buckets.into_values().collect(),
+            extra: self.extra.clone(),
+        })
+    }
+
+    fn rate(&self, numerator: Metric, denominator: Metric) -> Result<TimeSeries, AnalyticsError> {
+        self.ratio_series(|item| match (numerator.value(item), denominator.value(item)) {
+            (Some(numerator), Some(denominator)) if denominator > 0 => {
+                Some(numerator as f64 / denominator as f64 * 100.0)
+            },
+            _ => None,
+        })
+    }
+
+    fn ratio_series<F>(&self, value: F) -> Result<TimeSeries, AnalyticsError>
+    where F: Fn(&StatItem) -> Option<f64> {
+        let points = self.stats
+            .iter()
+            .map(|item| Ok(Point {
+                time: parse_time(&item.time)?,
+                value: value(item),
+            }))
+            .collect::<Result<_, AnalyticsError>>()?;
+
+        Ok(TimeSeries { points })
+    }
+}
+
+fn parse_time(time: &str) -> Result<DateTime<Utc>, AnalyticsError> {
+    get_stats::parse_time(time).ok_or_else(|| AnalyticsError::InvalidTime(time.to_string()))
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S UTC").to_string()
+}
+
+fn bucket_start(time: DateTime<Utc>, resolution: Resolution) -> DateTime<Utc> {
+    let (month, day, hour) = match resolution {
+        Resolution::Hour => (time.month(), time.day(), time.hour()),
+        Resolution::Day => (time.month(), time.day(), 0),
+        Resolution::Month => (time.month(), 1, 0),
+    };
+
+    Utc.with_ymd_and_hms(time.year(), month, day, hour, 0, 0)
+        .single()
+        .unwrap_or(time)
+}
+
+fn merge_item(into: &mut StatItem, item: &StatItem) {
+    merge(&mut into.accepted, &item.accepted, |into: &mut StatAccepted, item| {
+        into.outgoing += item.outgoing;
+        into.incoming += item.incoming;
+        into.total += item.total;
+    });
+    merge(&mut into.delivered, &item.delivered, |into: &mut StatDelivered, item| {
+        into.smtp += item.smtp;
+        into.http += item.http;
+        into.total += item.total;
+    });
+    merge(&mut into.failed, &item.failed, |into: &mut StatFailed, item| {
+        into.permanent.bounce += item.permanent.bounce;
+        into.permanent.delayed_bounce += item.permanent.delayed_bounce;
+        into.permanent.suppress_bounce += item.permanent.suppress_bounce;
+        into.permanent.suppress_unsubscribe += item.permanent.suppress_unsubscribe;
+        into.permanent.suppress_complaint += item.permanent.suppress_complaint;
+        into.permanent.total += item.permanent.total;
+        into.temporary.espblock += item.temporary.espblock;
+    });
+    merge(&mut into.opened, &item.opened, |into: &mut StatOpened, item| {
+        into.total += item.total;
+    });
+    merge(&mut into.clicked, &item.clicked, |into: &mut StatClicked, item| {
+        into.total += item.total;
+    });
+    merge(&mut into.unsubscribed, &item.unsubscribed, add_total);
+    merge(&mut into.complained, &item.complained, add_total);
+    merge(&mut into.stored, &item.stored, add_total);
+    clear_unique(into);
+}
+
+fn clear_unique(item: &mut StatItem) {
+    if let Some(opened) = item.opened.as_mut() {
+        opened.unique = None;
+    }
+
+    if let Some(clicked) = item.clicked.as_mut() {
+        clicked.unique = None;
+    }
+}
+
+fn merge<T: Clone>(into: &mut Option<T>, item: &Option<T>, add: impl Fn(&mut T, &T)) {
+    match (into.as_mut(), item) {
+        (Some(into), Some(item)) => add(into, item),
+        (None, Some(item)) => *into = Some(item.clone()),
+        (_, None) => {},
+    }
+}
+
+fn add_total(into: &mut StatTotal, item: &StatTotal) {
+    into.total += item.total;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> GetStatsResponse {
+        serde_json::from_str(r#"{
+            "start": "Mon, 28 Oct 2019 00:00:00 UTC",
+            "end": "Sat, 02 Nov 2019 00:00:00 UTC",
+            "resolution": "day",
+            "stats": [
+                {
+                    "time": "Thu, 31 Oct 2019 00:00:00 UTC",
+                    "accepted": { "outgoing": 200, "incoming": 0, "total": 200 },
+                    "delivered": { "smtp": 190, "http": 0, "total": 190 },
+                    "complained": { "total": 1 }
+                },
+                {
+                    "time": "Fri, 01 Nov 2019 00:00:00 UTC",
+                    "accepted": { "outgoing": 0, "incoming": 0, "total": 0 },
+                    "delivered": { "smtp": 0, "http": 0, "total": 0 },
+                    "opened": { "total": 20, "unique": 15 },
+                    "complained": { "total": 0 }
+                },
+                {
+                    "time": "Sat, 02 Nov 2019 00:00:00 UTC",
+                    "accepted": { "outgoing": 100, "incoming": 0, "total": 100 },
+                    "delivered": { "smtp": 100, "http": 0, "total": 100 },
+                    "opened": { "total": 30, "unique": 20 },
+                    "complained": { "total": 0 }
+                }
+            ]
+        }"#).unwrap()
+    }
+
+    #[test]
+    fn series_and_rates() {
+        let stats = response();
+
+        let delivered = stats.series(Metric::Delivered).unwrap();
+        assert_eq!(delivered.total(), 290.0);
+
+        let rates: Vec<_> = stats.delivery_rate().unwrap().points.into_iter().map(|p| p.value).collect();
+        assert_eq!(rates, vec![Some(95.0), None, Some(100.0)]);
+
+        let deltas: Vec<_> = delivered.deltas().points.into_iter().map(|p| p.value).collect();
+        assert_eq!(deltas, vec![Some(-190.0), Some(100.0)]);
+
+        assert_eq!(stats.series(Metric::Opened).unwrap().points[0].value, None);
+    }
+
+    #[test]
+    fn rebucket() {
+        let stats = response();
+
+        let monthly = stats.rebucket(Resolution::Month).unwrap();
+
+        assert_eq!(monthly.resolution, "month");
+        assert_eq!(monthly.stats.len(), 2);
+        assert_eq!(monthly.stats[0].time, "Tue, 01 Oct 2019 00:00:00 UTC");
+        assert_eq!(monthly.stats[1].time, "Fri, 01 Nov 2019 00:00:00 UTC");
+        assert_eq!(monthly.stats[1].accepted.as_ref().unwrap().total, 100);
+        assert_eq!(monthly.stats[1].complained.as_ref().unwrap().total, 0);
+
+        let opened = monthly.stats[1].opened.as_ref().unwrap();
+        assert_eq!(opened.total, 50);
+        assert_eq!(opened.unique, None);
+
+        let daily = stats.rebucket(Resolution::Day).unwrap();
+        assert_eq!(daily.stats[2].opened.as_ref().unwrap().unique, Some(20));
+
+        assert_eq!(
+            stats.rebucket(Resolution::Hour).unwrap_err(),
+            AnalyticsError::FinerResolution { from: Resolution::Day, to: Resolution::Hour },
+        );
+    }
+}
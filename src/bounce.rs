@@ -0,0 +1,389 @@
+//! Classification of bounces and failed deliveries.
+//!
+//! Mailgun reports the SMTP reply of the receiving server, which is made of a
+//! basic status code such as `550`, usually followed by an enhanced status
+//! code such as `5.1.1` and some free text. This module turns those into a
+//! [`BounceCategory`](enum.BounceCategory.html) and tells hard bounces apart
+//! from soft ones, for both [`BounceItem`](../get_bounces/struct.BounceItem.html)
+//! and failed [`EventItem`](../get_events/struct.EventItem.html)s.
+//!
+//! ### Example
+//!
+//! ```
+//! # use mailgun_sdk::bounce::{classify, BounceCategory};
+//! let class = classify(Some(550), "5.1.1 The email account that you tried to reach does not exist");
+//!
+//! assert_eq!(class.category, BounceCategory::BadMailbox);
+//! assert!(class.is_hard());
+//!
+//! let class = classify(Some(452), "4.2.2 The email account that you tried to reach is over quota");
+//!
+//! assert_eq!(class.category, BounceCategory::MailboxFull);
+//! assert!(class.is_soft());
+//! ```
+//!
+//! [RFC 3463](https://tools.ietf.org/html/rfc3463)
+
+use crate::get_bounces::{BounceItem, GetBouncesResponse};
+use crate::get_events::{EventDeliveryStatus, EventItem};
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Mailgun code for messages not sent to an address that bounced before.
+pub const SUPPRESSED_BOUNCE_CODE: u16 = 605;
+
+/// Mailgun code for messages not sent to an address that unsubscribed.
+pub const SUPPRESSED_UNSUBSCRIBE_CODE: u16 = 606;
+
+/// Mailgun code for messages not sent to an address that complained.
+pub const SUPPRESSED_COMPLAINT_CODE: u16 = 607;
+
+/// Likely reason for a bounce.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BounceCategory {
+    /// The mailbox does not exist or is disabled.
+    BadMailbox,
+    /// The mailbox is over its quota.
+    MailboxFull,
+    /// The message was refused by a policy of the receiving server, such as
+    /// failed authentication or rate limiting.
+    PolicyBlock,
+    /// The message, or the sending IP or domain, was considered spam.
+    SpamBlock,
+    /// The recipient's domain could not be found or routed to.
+    DnsFailure,
+    /// The message was not sent because the address unsubscribed.
+    Unsubscribed,
+    Other,
+}
+
+impl fmt::Display for BounceCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BadMailbox => write!(f, "bad mailbox"),
+            Self::MailboxFull => write!(f, "mailbox full"),
+            Self::PolicyBlock => write!(f, "policy block"),
+            Self::SpamBlock => write!(f, "spam block"),
+            Self::DnsFailure => write!(f, "DNS failure"),
+            Self::Unsubscribed => write!(f, "unsubscribed"),
+            Self::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// An enhanced status code, such as `5.1.1`.
+///
+/// ```
+/// # use mailgun_sdk::bounce::EnhancedStatus;
+/// let status: EnhancedStatus = "4.2.2".parse().unwrap();
+///
+/// assert!(!status.is_permanent());
+/// assert_eq!(status.to_string(), "4.2.2");
+/// assert!("6.0.0".parse::<EnhancedStatus>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EnhancedStatus {
+    /// `2` for success, `4` for a temporary failure or `5` for a permanent one.
+    pub class: u8,
+    pub subject: u16,
+    pub detail: u16,
+}
+
+impl EnhancedStatus {
+    /// Whether the failure is permanent.
+    pub fn is_permanent(&self) -> bool {
+        self.class == 5
+    }
+
+    /// Find the first enhanced status code in a server reply.
+    pub fn find(text: &str) -> Option<Self> {
+        text.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .find_map(|word| word.trim_end_matches('.').parse().ok())
+    }
+}
+
+impl FromStr for EnhancedStatus {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('.');
+
+        let class = parts.next().and_then(|v| v.parse().ok()).filter(|v| matches!(v, 2 | 4 | 5)).ok_or(())?;
+        let subject = parts.next().and_then(|v| v.parse().ok()).filter(|v| *v < 1000).ok_or(())?;
+        let detail = parts.next().and_then(|v| v.parse().ok()).filter(|v| *v < 1000).ok_or(())?;
+
+        if parts.next().is_some() {
+            return Err(());
+        }
+
+        Ok(Self { class, subject, detail })
+    }
+}
+
+impl fmt::Display for EnhancedStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.class, self.subject, self.detail)
+    }
+}
+
+/// Result of classifying a bounce.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BounceClass {
+    pub category: BounceCategory,
+    /// Basic SMTP status code, such as `550`.
+    pub code: Option<u16>,
+    pub enhanced: Option<EnhancedStatus>,
+    /// Whether the receiving server reported a permanent failure.
+    pub permanent: bool,
+}
+
+impl BounceClass {
+    /// Whether the address should not be sent to again.
+    ///
+    /// Full mailboxes are reported as permanent failures by some servers, but
+    /// usually clear up, so they are never hard bounces. Neither are messages
+    /// held back because the address unsubscribed, as the mailbox itself may
+    /// be fine.
+    pub fn is_hard(&self) -> bool {
+        self.permanent && !matches!(self.category, BounceCategory::MailboxFull | BounceCategory::Unsubscribed)
+    }
+
+    /// Whether sending to the address again may succeed.
+    pub fn is_soft(&self) -> bool {
+        !self.is_hard()
+    }
+}
+
+/// Classify a bounce from its basic SMTP status code and the text of the
+/// server's reply.
+///
+/// The enhanced status code is preferred over the basic one when the reply
+/// contains it. Known phrases in the reply are used to tell spam blocks apart
+/// from other policy blocks, and to classify replies without an enhanced
+/// status code. Short abbreviations such as `dns` or `rbl` only count as
+/// whole words, so they are not found inside addresses or host names.
+pub fn classify(code: Option<u16>, message: &str) -> BounceClass {
+    let enhanced = EnhancedStatus::find(message);
+    let text = message.to_ascii_lowercase();
+
+    let category = match code {
+        Some(SUPPRESSED_BOUNCE_CODE) => BounceCategory::BadMailbox,
+        Some(SUPPRESSED_COMPLAINT_CODE) => BounceCategory::SpamBlock,
+        Some(SUPPRESSED_UNSUBSCRIBE_CODE) => BounceCategory::Unsubscribed,
+        _ => match enhanced.map(enhanced_category).unwrap_or(BounceCategory::Other) {
+            BounceCategory::PolicyBlock if is_spam(&text) => BounceCategory::SpamBlock,
+            BounceCategory::Other => text_category(&text)
+                .or_else(|| code.and_then(code_category))
+                .unwrap_or(BounceCategory::Other),
+            category => category,
+        },
+    };
+
+    let permanent = match (enhanced, code) {
+        (Some(enhanced), _) => enhanced.is_permanent(),
+        (None, Some(code)) => (500..600).contains(&code) || (SUPPRESSED_BOUNCE_CODE..=SUPPRESSED_COMPLAINT_CODE).contains(&code),
+        (None, None) => false,
+    };
+
+    BounceClass { category, code, enhanced, permanent }
+}
+
+const SPAM_PHRASES: &[&str] = &["spam", "blacklist", "blocklist", "spamhaus", "reputation"];
+
+const SPAM_WORDS: &[&str] = &["rbl"];
+
+const MAILBOX_FULL_PHRASES: &[&str] = &["mailbox full", "mailbox is full", "quota", "insufficient storage"];
+
+const BAD_MAILBOX_PHRASES: &[&str] = &[
+    "user unknown",
+    "unknown user",
+    "no such user",
+    "does not exist",
+    "doesn't exist",
+    "invalid recipient",
+    "recipient not found",
+    "mailbox unavailable",
+    "mailbox not found",
+    "account disabled",
+];
+
+const DNS_PHRASES: &[&str] = &["domain not found", "host not found", "no mx", "nxdomain"];
+
+const DNS_WORDS: &[&str] = &["dns"];
+
+const POLICY_PHRASES: &[&str] = &["policy", "not allowed", "blocked", "denied", "rate limit", "too many"];
+
+fn enhanced_category(status: EnhancedStatus) -> BounceCategory {
+    match (status.subject, status.detail) {
+        (1, 1) | (1, 3) | (1, 6) | (2, 1) => BounceCategory::BadMailbox,
+        (1, 2) | (1, 10) | (4, 3) | (4, 4) => BounceCategory::DnsFailure,
+        (2, 2) => BounceCategory::MailboxFull,
+        (7, _) => BounceCategory::PolicyBlock,
+        _ => BounceCategory::Other,
+    }
+}
+
+fn text_category(text: &str) -> Option<BounceCategory> {
+    if is_spam(text) {
+        Some(BounceCategory::SpamBlock)
+    } else if contains_any(text, MAILBOX_FULL_PHRASES) {
+        Some(BounceCategory::MailboxFull)
+    } else if contains_any(text, BAD_MAILBOX_PHRASES) {
+        Some(BounceCategory::BadMailbox)
+    } else if contains_any(text, DNS_PHRASES) || contains_any_word(text, DNS_WORDS) {
+        Some(BounceCategory::DnsFailure)
+    } else if contains_any(text, POLICY_PHRASES) {
+        Some(BounceCategory::PolicyBlock)
+    } else {
+        None
+    }
+}
+
+fn code_category(code: u16) -> Option<BounceCategory> {
+    match code {
+        452 | 552 => Some(BounceCategory::MailboxFull),
+        _ => None,
+    }
+}
+
+fn is_spam(text: &str) -> bool {
+    contains_any(text, SPAM_PHRASES) || contains_any_word(text, SPAM_WORDS)
+}
+
+fn contains_any(text: &str, phrases: &[&str]) -> bool {
+    phrases.iter().any(|phrase| text.contains(phrase))
+}
+
+fn contains_any_word(text: &str, words: &[&str]) -> bool {
+    let is_boundary = |c: Option<char>| !matches!(c, Some(c) if c.is_ascii_alphanumeric());
+
+    words.iter().any(|word| {
+        text.match_indices(word).any(|(start, _)| {
+            is_boundary(text[..start].chars().next_back()) && is_boundary(text[start + word.len()..].chars().next())
+        })
+    })
+}
+
+impl BounceItem {
+    /// Classify this bounce.
+    pub fn classify(&self) -> BounceClass {
+        classify(self.code.trim().parse().ok(), &self.error)
+    }
+}
+
+impl GetBouncesResponse {
+    /// Number of bounces in each category.
+    pub fn categories(&self) -> HashMap<BounceCategory, usize> {
+        let mut counts = HashMap::new();
+
+        for item in self.items.iter() {
+            *counts.entry(item.classify().category).or_insert(0) += 1;
+        }
+
+        counts
+    }
+}
+
+impl EventDeliveryStatus {
+    /// Classify the delivery attempt from the receiving server's reply.
+    pub fn classify(&self) -> BounceClass {
+        let message = [self.message.as_deref(), self.description.as_deref()]
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        classify(self.code.and_then(|code| u16::try_from(code).ok()), &message)
+    }
+}
+
+impl EventItem {
+    /// Classify a failed event. Returns `None` for events without a delivery
+    /// status.
+    ///
+    /// The event's severity, when present, takes precedence over the status
+    /// codes in deciding whether the failure is permanent.
+    pub fn classify(&self) -> Option<BounceClass> {
+        let mut class = self.delivery_status.as_ref()?.classify();
+
+        match self.severity.as_deref() {
+            Some("permanent") => class.permanent = true,
+            Some("temporary") => class.permanent = false,
+            _ => {},
+        }
+
+        Some(class)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_replies() {
+        let class = classify(Some(550), "5.7.1 Message rejected as spam by Content Filtering.");
+        assert_eq!(class.category, BounceCategory::SpamBlock);
+        assert_eq!(class.enhanced, Some(EnhancedStatus { class: 5, subject: 7, detail: 1 }));
+
+        let class = classify(Some(550), "5.7.26 Unauthenticated email is not accepted due to DMARC policy");
+        assert_eq!(class.category, BounceCategory::PolicyBlock);
+
+        let class = classify(Some(550), "Requested action not taken: mailbox unavailable");
+        assert_eq!(class.category, BounceCategory::BadMailbox);
+        assert!(class.is_hard());
+
+        let class = classify(Some(552), "5.2.2 Mailbox full");
+        assert_eq!(class.category, BounceCategory::MailboxFull);
+        assert!(class.permanent);
+        assert!(class.is_soft());
+
+        let class = classify(Some(550), "5.4.4 Unable to route");
+        assert_eq!(class.category, BounceCategory::DnsFailure);
+
+        let class = classify(Some(421), "Try again later");
+        assert_eq!(class.category, BounceCategory::Other);
+        assert!(class.is_soft());
+
+        let class = classify(Some(SUPPRESSED_BOUNCE_CODE), "Not delivering to previously bounced address");
+        assert_eq!(class.category, BounceCategory::BadMailbox);
+        assert!(class.is_hard());
+
+        let class = classify(Some(SUPPRESSED_UNSUBSCRIBE_CODE), "Not delivering to a user who unsubscribed");
+        assert_eq!(class.category, BounceCategory::Unsubscribed);
+        assert!(class.permanent);
+        assert!(class.is_soft());
+    }
+
+    #[test]
+    fn classify_short_words() {
+        let class = classify(Some(550), "Sending IP listed on an RBL");
+        assert_eq!(class.category, BounceCategory::SpamBlock);
+
+        let class = classify(Some(550), "Mailbox marblehead@example.com does not exist");
+        assert_eq!(class.category, BounceCategory::BadMailbox);
+
+        let class = classify(Some(451), "Temporary DNS lookup failure");
+        assert_eq!(class.category, BounceCategory::DnsFailure);
+
+        let class = classify(Some(550), "Message from mydnsprovider.com blocked by policy");
+        assert_eq!(class.category, BounceCategory::PolicyBlock);
+    }
+
+    #[test]
+    fn classify_bounce_item() {
+        let item: BounceItem = serde_json::from_str(r#"{
+            "address": "alice@example.com",
+            "code": "550",
+            "error": "550 5.1.1 The email account that you tried to reach does not exist.",
+            "created_at": "Fri, 21 Oct 2011 11:02:55 GMT"
+        }"#).unwrap();
+
+        assert_eq!(item.classify().category, BounceCategory::BadMailbox);
+        assert!(item.classify().is_hard());
+    }
+}
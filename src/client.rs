@@ -1,323 +1,1699 @@
 use crate::endpoints::{
+    account_stats::{GetFilteredStatsParamList, GetFilteredStatsResponse},
     get_bounces::{GetBouncesParamList, GetBouncesResponse},
     get_complaints::{GetComplaintsParamList, GetComplaintsResponse},
-    get_events::{GetEventsParamList, GetEventsResponse},
-    get_stats::{GetStatsParamList, GetStatsResponse},
+    get_events::{EventStorage, GetEventsParamList, GetEventsResponse},
+    get_stats::{
+        GetDomainCountriesResponse,
+        GetDomainDevicesResponse,
+        GetDomainProvidersResponse,
+        GetStatsParamList,
+        GetStatsResponse,
+    },
+    get_stored_message::{StoredMessage, StoredMessageMime},
     get_unsubscribes::{GetUnsubscribesParamList, GetUnsubscribesResponse},
     get_whitelists::{GetWhitelistsParamList, GetWhitelistsResponse},
-    send_message::{SendMessageParamList, SendMessageResponse},
+    mailing_lists::{
+        AddListMembersResponse,
+        DeleteListMemberResponse,
+        DeleteMailingListResponse,
+        GetListMembersParamList,
+        GetListMembersResponse,
+        GetMailingListsParamList,
+        GetMailingListsResponse,
+        ListMember,
+        ListMemberParamList,
+        ListMemberResponse,
+        MailingListParamList,
+        MailingListResponse,
+        MAX_BULK_MEMBERS,
+    },
+    routes::{
+        DeleteRouteResponse,
+        GetRoutesParamList,
+        GetRoutesResponse,
+        RouteParamList,
+        RouteResponse,
+        UpdateRouteResponse,
+    },
+    send_message::{SendMessageParam, SendMessageParamList, SendMessageResponse},
+    tags::{
+        GetTagCountriesResponse,
+        GetTagDevicesResponse,
+        GetTagProvidersResponse,
+        GetTagStatsResponse,
+        GetTagsParamList,
+        GetTagsResponse,
+        Tag,
+        TagMessageResponse,
+    },
+    validations::{
+        BulkValidationJob,
+        CancelBulkValidationResponse,
+        CreateBulkValidationResponse,
+        DownloadFormat,
+        GetBulkValidationsParamList,
+        GetBulkValidationsResponse,
+        ValidateAddressParamList,
+        ValidateAddressResponse,
+    },
 };
-use crate::param::{Param, ParamError};
-use crate::MAILGUN_API_BASE;
+use crate::endpoints::{decode_lenient, ItemList};
+use crate::error::ApiError;
+use crate::instrument::Call;
+use crate::middleware::{Middleware, MiddlewareChain, Request};
+use crate::multipart::Multipart;
+use crate::request::{ApiRequest, Body, Method};
+use crate::secret::ApiKey;
+use crate::response::{self, Response, ResponseMeta};
+use crate::param::ParamError;
+use crate::{MAILGUN_API_BASE, MAILGUN_API_V4_BASE};
 use thiserror::Error;
 
-use std::io;
+use std::fmt;
+use std::io::{self, Read};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+/// Time allowed to connect to the API, unless set with a
+/// [`ClientBuilder`](struct.ClientBuilder.html).
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time allowed for each read and write of a request, unless set with a
+/// [`ClientBuilder`](struct.ClientBuilder.html).
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// `User-Agent` header identifying this crate and its version.
+const USER_AGENT: &str = concat!("mailgun-sdk-rust/", env!("CARGO_PKG_VERSION"));
 
-#[derive(Debug)]
 pub struct Client {
-    api_key: String,
+    api_key: RwLock<ApiKey>,
     domain: String,
+    lenient: bool,
+    agent: ureq::Agent,
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    write_timeout: Duration,
+    proxy: Option<ureq::Proxy>,
+    tls_config: Option<Arc<rustls::ClientConfig>>,
+    user_agent: String,
+    middleware: MiddlewareChain,
 }
 
-impl Client {
-    /// Create a new client.
-    pub fn new(api_key: &str, domain: &str) -> Self {
-        Self {
-            api_key: api_key.to_string(),
-            domain: domain.to_string(),
+impl Client {
+    /// Create a new client, with default timeouts and a connection pool of
+    /// its own.
+    pub fn new(api_key: &str, domain: &str) -> Self {
+        Self::with_api_key(ApiKey::new(api_key), domain)
+    }
+
+    /// Create a new client from an [`ApiKey`](struct.ApiKey.html), such as
+    /// one loaded from the environment.
+    pub fn with_api_key(api_key: ApiKey, domain: &str) -> Self {
+        Self {
+            api_key: RwLock::new(api_key),
+            domain: domain.to_string(),
+            lenient: false,
+            agent: ureq::agent(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_TIMEOUT,
+            write_timeout: DEFAULT_TIMEOUT,
+            proxy: None,
+            tls_config: None,
+            user_agent: USER_AGENT.to_string(),
+            middleware: MiddlewareChain::default(),
+        }
+    }
+
+    /// Configure a new client.
+    pub fn builder(api_key: &str, domain: &str) -> ClientBuilder {
+        ClientBuilder::new(api_key, domain)
+    }
+
+    /// Replace the API key used by the following requests, such as after it
+    /// was rotated. Requests already being sent keep the previous key.
+    pub fn rotate_api_key(&self, api_key: ApiKey) {
+        *self.api_key.write().unwrap_or_else(PoisonError::into_inner) = api_key;
+    }
+
+    /// Keep going when single items of a list response cannot be decoded,
+    /// such as an event with an unexpected shape. The items are reported in
+    /// the response's `invalid_items` field instead.
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+
+        self
+    }
+
+    /// Run `middleware` around every request made by this client, after any
+    /// middleware added before it.
+    pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middleware.push(middleware);
+
+        self
+    }
+
+    /// Make one or more API calls, and return the parsed body of the last
+    /// one along with its status, headers, request ID, rate limit and
+    /// timing.
+    ///
+    /// ```no_run
+    /// # use mailgun_sdk::{Client, get_events::GetEventsParamList};
+    /// # let client = Client::new("", "");
+    /// let response = client.with_meta(|client| client.get_events(GetEventsParamList::default())).unwrap();
+    ///
+    /// println!("{:?} took {:?}", response.meta.request_id, response.meta.elapsed);
+    /// println!("{} events", response.body.items.len());
+    /// ```
+    ///
+    /// Returns [`ClientError::NoResponse`](enum.ClientError.html#variant.NoResponse)
+    /// if `call` returns `Ok` without making a request with this client.
+    pub fn with_meta<T, F>(&self, call: F) -> Result<Response<T>, ClientError>
+    where F: FnOnce(&Self) -> Result<T, ClientError> {
+        let (result, meta) = response::capture(|| call(self));
+
+        Ok(Response {
+            body: result?,
+            meta: meta.ok_or(ClientError::NoResponse)?,
+        })
+    }
+
+    /// Make an API call from a URL.
+    ///
+    /// This will primarily be used with pagination URLs.
+    pub fn call<T>(&self, url: &str) -> Result<T, ClientError>
+    where T: serde::de::DeserializeOwned {
+        self.fetch(ApiRequest::get(url.to_string()).endpoint("call"))
+    }
+
+    /// Make an API call from a pagination URL of a list response, decoding it
+    /// leniently if the client is [`lenient`](#method.lenient).
+    pub fn call_list<T: ItemList>(&self, url: &str) -> Result<T, ClientError> {
+        self.fetch_list(ApiRequest::get(url.to_string()).endpoint("call"))
+    }
+
+    /// Make an API call to an endpoint of the domain that has no method of its
+    /// own. `path` is relative to the domain, such as `tracking` for
+    /// `/v3/{domain}/tracking`.
+    ///
+    /// `params` are sent as the query string for `GET` and `DELETE` requests,
+    /// and as a form body otherwise.
+    pub fn request<T>(&self, method: Method, path: &str, params: &[(&str, &str)]) -> Result<T, ClientError>
+    where T: serde::de::DeserializeOwned {
+        self.fetch(self.custom_request(method, path, params))
+    }
+
+    fn custom_request(&self, method: Method, path: &str, params: &[(&str, &str)]) -> ApiRequest {
+        let url = format!("{}/{}/{}", MAILGUN_API_BASE, self.domain, path.trim_start_matches('/'));
+        let pairs = params.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        let mut request = ApiRequest::new(method, url).endpoint("request");
+
+        match method {
+            Method::Get | Method::Delete => request.query = pairs,
+            Method::Post | Method::Put => request = request.form_pairs(pairs),
+        }
+
+        request
+    }
+
+    /// View all bounces.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#bounces)
+    pub fn get_bounces(&self, params: GetBouncesParamList) -> Result<GetBouncesResponse, ClientError> {
+        self.fetch_list(self.get_bounces_request(params)?)
+    }
+
+    /// Same as [`get_bounces`](#method.get_bounces), but returns the
+    /// response body as raw JSON.
+    pub fn get_bounces_raw(&self, params: GetBouncesParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_bounces_request(params)?)
+    }
+
+    fn get_bounces_request(&self, params: GetBouncesParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/bounces", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_bounces").query(&params.values)?)
+    }
+
+    /// View all complaints.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#view-all-complaints)
+    pub fn get_complaints(&self, params: GetComplaintsParamList) -> Result<GetComplaintsResponse, ClientError> {
+        self.fetch_list(self.get_complaints_request(params)?)
+    }
+
+    /// Same as [`get_complaints`](#method.get_complaints), but returns the
+    /// response body as raw JSON.
+    pub fn get_complaints_raw(&self, params: GetComplaintsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_complaints_request(params)?)
+    }
+
+    fn get_complaints_request(&self, params: GetComplaintsParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/complaints", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_complaints").query(&params.values)?)
+    }
+
+    /// View all events.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-events.html)
+    pub fn get_events(&self, params: GetEventsParamList) -> Result<GetEventsResponse, ClientError> {
+        self.fetch_list(self.get_events_request(params)?)
+    }
+
+    /// Same as [`get_events`](#method.get_events), but returns the
+    /// response body as raw JSON.
+    pub fn get_events_raw(&self, params: GetEventsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_events_request(params)?)
+    }
+
+    fn get_events_request(&self, params: GetEventsParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/events", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_events").query(&params.values)?)
+    }
+
+    /// View all stats.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-stats.html)
+    pub fn get_stats(&self, params: GetStatsParamList) -> Result<GetStatsResponse, ClientError> {
+        self.fetch(self.get_stats_request(params)?)
+    }
+
+    /// Same as [`get_stats`](#method.get_stats), but returns the
+    /// response body as raw JSON.
+    pub fn get_stats_raw(&self, params: GetStatsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_stats_request(params)?)
+    }
+
+    fn get_stats_request(&self, params: GetStatsParamList) -> Result<ApiRequest, ClientError> {
+        params.validate()?;
+
+        let url = format!("{}/{}/stats/total", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_stats").query(&params.values)?)
+    }
+
+    /// View stats for all domains of the account.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-stats.html)
+    pub fn get_account_stats(&self, params: GetStatsParamList) -> Result<GetStatsResponse, ClientError> {
+        self.fetch(self.get_account_stats_request(params)?)
+    }
+
+    /// Same as [`get_account_stats`](#method.get_account_stats), but returns the
+    /// response body as raw JSON.
+    pub fn get_account_stats_raw(&self, params: GetStatsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_account_stats_request(params)?)
+    }
+
+    fn get_account_stats_request(&self, params: GetStatsParamList) -> Result<ApiRequest, ClientError> {
+        params.validate()?;
+
+        let url = format!("{}/stats/total", MAILGUN_API_BASE);
+
+        Ok(ApiRequest::get(url).endpoint("get_account_stats").query(&params.values)?)
+    }
+
+    /// View stats for all domains of the account, filtered and grouped by
+    /// domain, tag, subaccount or IP pool.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-stats.html)
+    pub fn get_filtered_stats(&self, params: GetFilteredStatsParamList) -> Result<GetFilteredStatsResponse, ClientError> {
+        self.fetch(self.get_filtered_stats_request(params)?)
+    }
+
+    /// Same as [`get_filtered_stats`](#method.get_filtered_stats), but returns the
+    /// response body as raw JSON.
+    pub fn get_filtered_stats_raw(&self, params: GetFilteredStatsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_filtered_stats_request(params)?)
+    }
+
+    fn get_filtered_stats_request(&self, params: GetFilteredStatsParamList) -> Result<ApiRequest, ClientError> {
+        params.validate()?;
+
+        let url = format!("{}/stats/filter", MAILGUN_API_BASE);
+
+        Ok(ApiRequest::get(url).endpoint("get_filtered_stats").query(&params.values)?)
+    }
+
+    /// View stats for the domain, aggregated by country.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-stats.html)
+    pub fn get_domain_countries(&self) -> Result<GetDomainCountriesResponse, ClientError> {
+        self.fetch(self.get_domain_countries_request()?)
+    }
+
+    /// Same as [`get_domain_countries`](#method.get_domain_countries), but returns the
+    /// response body as raw JSON.
+    pub fn get_domain_countries_raw(&self) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_domain_countries_request()?)
+    }
+
+    fn get_domain_countries_request(&self) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/aggregates/countries", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_domain_countries"))
+    }
+
+    /// View stats for the domain, aggregated by email provider.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-stats.html)
+    pub fn get_domain_providers(&self) -> Result<GetDomainProvidersResponse, ClientError> {
+        self.fetch(self.get_domain_providers_request()?)
+    }
+
+    /// Same as [`get_domain_providers`](#method.get_domain_providers), but returns the
+    /// response body as raw JSON.
+    pub fn get_domain_providers_raw(&self) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_domain_providers_request()?)
+    }
+
+    fn get_domain_providers_request(&self) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/aggregates/providers", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_domain_providers"))
+    }
+
+    /// View stats for the domain, aggregated by device type.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-stats.html)
+    pub fn get_domain_devices(&self) -> Result<GetDomainDevicesResponse, ClientError> {
+        self.fetch(self.get_domain_devices_request()?)
+    }
+
+    /// Same as [`get_domain_devices`](#method.get_domain_devices), but returns the
+    /// response body as raw JSON.
+    pub fn get_domain_devices_raw(&self) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_domain_devices_request()?)
+    }
+
+    fn get_domain_devices_request(&self) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/aggregates/devices", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_domain_devices"))
+    }
+
+    /// Retrieve a stored message as parsed fields.
+    ///
+    /// The storage URL must be an HTTPS URL of a `mailgun.net` host, as the
+    /// API key is sent to it.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-sending.html#retrieving-stored-messages)
+    pub fn get_stored_message(&self, storage: &EventStorage) -> Result<StoredMessage, ClientError> {
+        self.fetch(self.get_stored_message_request(storage)?)
+    }
+
+    /// Same as [`get_stored_message`](#method.get_stored_message), but returns the
+    /// response body as raw JSON.
+    pub fn get_stored_message_raw(&self, storage: &EventStorage) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_stored_message_request(storage)?)
+    }
+
+    fn get_stored_message_request(&self, storage: &EventStorage) -> Result<ApiRequest, ClientError> {
+        let request = ApiRequest::get(storage_url(storage)?)
+            .endpoint("get_stored_message")
+            .header("Accept", "application/json");
+
+        Ok(request)
+    }
+
+    /// Retrieve a stored message as a raw MIME message.
+    ///
+    /// The storage URL must be an HTTPS URL of a `mailgun.net` host, as the
+    /// API key is sent to it.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-sending.html#retrieving-stored-messages)
+    pub fn get_stored_message_mime(&self, storage: &EventStorage) -> Result<StoredMessageMime, ClientError> {
+        self.fetch(self.get_stored_message_mime_request(storage)?)
+    }
+
+    /// Same as [`get_stored_message_mime`](#method.get_stored_message_mime), but returns the
+    /// response body as raw JSON.
+    pub fn get_stored_message_mime_raw(&self, storage: &EventStorage) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_stored_message_mime_request(storage)?)
+    }
+
+    fn get_stored_message_mime_request(&self, storage: &EventStorage) -> Result<ApiRequest, ClientError> {
+        let request = ApiRequest::get(storage_url(storage)?)
+            .endpoint("get_stored_message_mime")
+            .header("Accept", "message/rfc2822");
+
+        Ok(request)
+    }
+
+    /// Resend a stored message to new recipients.
+    ///
+    /// `to` accepts the same comma-separated format as the `To` parameter
+    /// when sending a message. The storage URL must be an HTTPS URL of a `mailgun.net` host, as the
+    /// API key is sent to it.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-sending.html#resending-messages)
+    pub fn resend_stored_message(&self, storage: &EventStorage, to: &str) -> Result<SendMessageResponse, ClientError> {
+        self.fetch(self.resend_stored_message_request(storage, to)?)
+    }
+
+    /// Same as [`resend_stored_message`](#method.resend_stored_message), but returns the
+    /// response body as raw JSON.
+    pub fn resend_stored_message_raw(&self, storage: &EventStorage, to: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.resend_stored_message_request(storage, to)?)
+    }
+
+    fn resend_stored_message_request(&self, storage: &EventStorage, to: &str) -> Result<ApiRequest, ClientError> {
+        let request = ApiRequest::post(storage_url(storage)?)
+            .endpoint("resend_stored_message")
+            .form(&[SendMessageParam::<String>::To(to)])?;
+
+        Ok(request)
+    }
+
+    /// Build the storage location of a stored message from its key, for use
+    /// with `get_stored_message`, `get_stored_message_mime` and
+    /// `resend_stored_message`.
+    pub fn stored_message_storage(&self, key: &str) -> EventStorage {
+        EventStorage {
+            url: format!("{}/domains/{}/messages/{}", MAILGUN_API_BASE, self.domain, path_segment(key)),
+            key: key.to_string(),
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    /// View all unsubscribes.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#unsubscribes)
+    pub fn get_unsubscribes(&self, params: GetUnsubscribesParamList) -> Result<GetUnsubscribesResponse, ClientError> {
+        self.fetch_list(self.get_unsubscribes_request(params)?)
+    }
+
+    /// Same as [`get_unsubscribes`](#method.get_unsubscribes), but returns the
+    /// response body as raw JSON.
+    pub fn get_unsubscribes_raw(&self, params: GetUnsubscribesParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_unsubscribes_request(params)?)
+    }
+
+    fn get_unsubscribes_request(&self, params: GetUnsubscribesParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/unsubscribes", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_unsubscribes").query(&params.values)?)
+    }
+
+    /// View all whitelist records.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#view-all-whitelist-records)
+    pub fn get_whitelists(&self, params: GetWhitelistsParamList) -> Result<GetWhitelistsResponse, ClientError> {
+        self.fetch_list(self.get_whitelists_request(params)?)
+    }
+
+    /// Same as [`get_whitelists`](#method.get_whitelists), but returns the
+    /// response body as raw JSON.
+    pub fn get_whitelists_raw(&self, params: GetWhitelistsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_whitelists_request(params)?)
+    }
+
+    fn get_whitelists_request(&self, params: GetWhitelistsParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/whitelists", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_whitelists").query(&params.values)?)
+    }
+
+    pub fn send_message(&self, params: SendMessageParamList<String>) -> Result<SendMessageResponse, ClientError> {
+        self.fetch(self.send_message_request(params)?)
+    }
+
+    /// Same as [`send_message`](#method.send_message), but returns the
+    /// response body as raw JSON.
+    pub fn send_message_raw(&self, params: SendMessageParamList<String>) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.send_message_request(params)?)
+    }
+
+    fn send_message_request(&self, params: SendMessageParamList<String>) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/messages", MAILGUN_API_BASE, self.domain);
+
+        let mut request = ApiRequest::post(url).endpoint("send_message");
+
+        // TODO: If a parameter is an attachment, send a "multipart/form-data" body.
+        request.query = params.to_pairs()?;
+
+        Ok(request)
+    }
+
+    /// Send a message that is already in MIME format.
+    ///
+    /// Only `o:`, `h:` and `v:` options are accepted in `params`; any other
+    /// parameter results in a `ParamError::Unsupported` error.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-sending.html#sending)
+    pub fn send_mime(&self, to: &str, mime: &[u8], params: SendMessageParamList<String>) -> Result<SendMessageResponse, ClientError> {
+        self.fetch(self.send_mime_request(to, mime, params)?)
+    }
+
+    /// Same as [`send_mime`](#method.send_mime), but returns the
+    /// response body as raw JSON.
+    pub fn send_mime_raw(&self, to: &str, mime: &[u8], params: SendMessageParamList<String>) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.send_mime_request(to, mime, params)?)
+    }
+
+    fn send_mime_request(&self, to: &str, mime: &[u8], params: SendMessageParamList<String>) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/messages.mime", MAILGUN_API_BASE, self.domain);
+
+        let mut form = Multipart::new();
+
+        let (key, value) = SendMessageParam::<String>::To(to).render(!params.skip_validation)?;
+        form.text(&key, &value);
+
+        for (param, (key, value)) in params.values.iter().zip(params.to_pairs()?) {
+            if !param.is_option() {
+                return Err(ParamError::Unsupported(key).into());
+            }
+
+            form.text(&key, &value);
+        }
+
+        form.file("message", "message.mime", "message/rfc822", mime);
+
+        Ok(ApiRequest::post(url).endpoint("send_mime").multipart(form))
+    }
+
+    /// View all mailing lists.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn get_mailing_lists(&self, params: GetMailingListsParamList) -> Result<GetMailingListsResponse, ClientError> {
+        self.fetch_list(self.get_mailing_lists_request(params)?)
+    }
+
+    /// Same as [`get_mailing_lists`](#method.get_mailing_lists), but returns the
+    /// response body as raw JSON.
+    pub fn get_mailing_lists_raw(&self, params: GetMailingListsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_mailing_lists_request(params)?)
+    }
+
+    fn get_mailing_lists_request(&self, params: GetMailingListsParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/pages", MAILGUN_API_BASE);
+
+        Ok(ApiRequest::get(url).endpoint("get_mailing_lists").query(&params.values)?)
+    }
+
+    /// View a single mailing list.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn get_mailing_list(&self, address: &str) -> Result<MailingListResponse, ClientError> {
+        self.fetch(self.get_mailing_list_request(address)?)
+    }
+
+    /// Same as [`get_mailing_list`](#method.get_mailing_list), but returns the
+    /// response body as raw JSON.
+    pub fn get_mailing_list_raw(&self, address: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_mailing_list_request(address)?)
+    }
+
+    fn get_mailing_list_request(&self, address: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}", MAILGUN_API_BASE, path_segment(address));
+
+        Ok(ApiRequest::get(url).endpoint("get_mailing_list"))
+    }
+
+    /// Create a new mailing list.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn create_mailing_list(&self, params: MailingListParamList) -> Result<MailingListResponse, ClientError> {
+        self.fetch(self.create_mailing_list_request(params)?)
+    }
+
+    /// Same as [`create_mailing_list`](#method.create_mailing_list), but returns the
+    /// response body as raw JSON.
+    pub fn create_mailing_list_raw(&self, params: MailingListParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.create_mailing_list_request(params)?)
+    }
+
+    fn create_mailing_list_request(&self, params: MailingListParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists", MAILGUN_API_BASE);
+
+        Ok(ApiRequest::post(url).endpoint("create_mailing_list").form(&params.values)?)
+    }
+
+    /// Update an existing mailing list.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn update_mailing_list(&self, address: &str, params: MailingListParamList) -> Result<MailingListResponse, ClientError> {
+        self.fetch(self.update_mailing_list_request(address, params)?)
+    }
+
+    /// Same as [`update_mailing_list`](#method.update_mailing_list), but returns the
+    /// response body as raw JSON.
+    pub fn update_mailing_list_raw(&self, address: &str, params: MailingListParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.update_mailing_list_request(address, params)?)
+    }
+
+    fn update_mailing_list_request(&self, address: &str, params: MailingListParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}", MAILGUN_API_BASE, path_segment(address));
+
+        Ok(ApiRequest::put(url).endpoint("update_mailing_list").form(&params.values)?)
+    }
+
+    /// Delete a mailing list.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn delete_mailing_list(&self, address: &str) -> Result<DeleteMailingListResponse, ClientError> {
+        self.fetch(self.delete_mailing_list_request(address)?)
+    }
+
+    /// Same as [`delete_mailing_list`](#method.delete_mailing_list), but returns the
+    /// response body as raw JSON.
+    pub fn delete_mailing_list_raw(&self, address: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.delete_mailing_list_request(address)?)
+    }
+
+    fn delete_mailing_list_request(&self, address: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}", MAILGUN_API_BASE, path_segment(address));
+
+        Ok(ApiRequest::delete(url).endpoint("delete_mailing_list"))
+    }
+
+    /// View the members of a mailing list.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn get_list_members(&self, list: &str, params: GetListMembersParamList) -> Result<GetListMembersResponse, ClientError> {
+        self.fetch_list(self.get_list_members_request(list, params)?)
+    }
+
+    /// Same as [`get_list_members`](#method.get_list_members), but returns the
+    /// response body as raw JSON.
+    pub fn get_list_members_raw(&self, list: &str, params: GetListMembersParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_list_members_request(list, params)?)
+    }
+
+    fn get_list_members_request(&self, list: &str, params: GetListMembersParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}/members/pages", MAILGUN_API_BASE, path_segment(list));
+
+        Ok(ApiRequest::get(url).endpoint("get_list_members").query(&params.values)?)
+    }
+
+    /// View a single member of a mailing list.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn get_list_member(&self, list: &str, member: &str) -> Result<ListMemberResponse, ClientError> {
+        self.fetch(self.get_list_member_request(list, member)?)
+    }
+
+    /// Same as [`get_list_member`](#method.get_list_member), but returns the
+    /// response body as raw JSON.
+    pub fn get_list_member_raw(&self, list: &str, member: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_list_member_request(list, member)?)
+    }
+
+    fn get_list_member_request(&self, list: &str, member: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}/members/{}", MAILGUN_API_BASE, path_segment(list), path_segment(member));
+
+        Ok(ApiRequest::get(url).endpoint("get_list_member"))
+    }
+
+    /// Add a single member to a mailing list.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn add_list_member(&self, list: &str, params: ListMemberParamList) -> Result<ListMemberResponse, ClientError> {
+        self.fetch(self.add_list_member_request(list, params)?)
+    }
+
+    /// Same as [`add_list_member`](#method.add_list_member), but returns the
+    /// response body as raw JSON.
+    pub fn add_list_member_raw(&self, list: &str, params: ListMemberParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.add_list_member_request(list, params)?)
+    }
+
+    fn add_list_member_request(&self, list: &str, params: ListMemberParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}/members", MAILGUN_API_BASE, path_segment(list));
+
+        Ok(ApiRequest::post(url).endpoint("add_list_member").form(&params.values)?)
+    }
+
+    /// Update a single member of a mailing list.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn update_list_member(&self, list: &str, member: &str, params: ListMemberParamList) -> Result<ListMemberResponse, ClientError> {
+        self.fetch(self.update_list_member_request(list, member, params)?)
+    }
+
+    /// Same as [`update_list_member`](#method.update_list_member), but returns the
+    /// response body as raw JSON.
+    pub fn update_list_member_raw(&self, list: &str, member: &str, params: ListMemberParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.update_list_member_request(list, member, params)?)
+    }
+
+    fn update_list_member_request(&self, list: &str, member: &str, params: ListMemberParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}/members/{}", MAILGUN_API_BASE, path_segment(list), path_segment(member));
+
+        Ok(ApiRequest::put(url).endpoint("update_list_member").form(&params.values)?)
+    }
+
+    /// Remove a single member from a mailing list.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn delete_list_member(&self, list: &str, member: &str) -> Result<DeleteListMemberResponse, ClientError> {
+        self.fetch(self.delete_list_member_request(list, member)?)
+    }
+
+    /// Same as [`delete_list_member`](#method.delete_list_member), but returns the
+    /// response body as raw JSON.
+    pub fn delete_list_member_raw(&self, list: &str, member: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.delete_list_member_request(list, member)?)
+    }
+
+    fn delete_list_member_request(&self, list: &str, member: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}/members/{}", MAILGUN_API_BASE, path_segment(list), path_segment(member));
+
+        Ok(ApiRequest::delete(url).endpoint("delete_list_member"))
+    }
+
+    /// Add up to 1000 members to a mailing list in a single request.
+    ///
+    /// If `upsert` is `true`, existing members are updated instead of
+    /// causing the request to fail.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn add_list_members(&self, list: &str, members: &[ListMember], upsert: bool) -> Result<AddListMembersResponse, ClientError> {
+        self.fetch(self.add_list_members_request(list, members, upsert)?)
+    }
+
+    /// Same as [`add_list_members`](#method.add_list_members), but returns the
+    /// response body as raw JSON.
+    pub fn add_list_members_raw(&self, list: &str, members: &[ListMember], upsert: bool) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.add_list_members_request(list, members, upsert)?)
+    }
+
+    fn add_list_members_request(&self, list: &str, members: &[ListMember], upsert: bool) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}/members.json", MAILGUN_API_BASE, path_segment(list));
+
+        if members.len() > MAX_BULK_MEMBERS {
+            return Err(ParamError::InvalidValue(
+                "members".to_string(),
+                format!("at most {} members can be added at once, got {}", MAX_BULK_MEMBERS, members.len()),
+            ).into());
+        }
+
+        let members = serde_json::to_string(members)
+            .map_err(|error| ParamError::InvalidJson("members".to_string(), error))?;
+        let upsert = if upsert { "yes" } else { "no" };
+
+        let request = ApiRequest::post(url)
+            .endpoint("add_list_members")
+            .form_pairs(vec![("members".to_string(), members), ("upsert".to_string(), upsert.to_string())]);
+
+        Ok(request)
+    }
+
+    /// Add members to a mailing list from a CSV file.
+    ///
+    /// The CSV must have an `address` column, and may have `name`, `vars` and
+    /// `subscribed` columns. If `upsert` is `true`, existing members are
+    /// updated instead of causing the request to fail.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html#mailing-lists)
+    pub fn upload_list_members(&self, list: &str, csv: &[u8], upsert: bool) -> Result<AddListMembersResponse, ClientError> {
+        self.fetch(self.upload_list_members_request(list, csv, upsert)?)
+    }
+
+    /// Same as [`upload_list_members`](#method.upload_list_members), but returns the
+    /// response body as raw JSON.
+    pub fn upload_list_members_raw(&self, list: &str, csv: &[u8], upsert: bool) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.upload_list_members_request(list, csv, upsert)?)
+    }
+
+    fn upload_list_members_request(&self, list: &str, csv: &[u8], upsert: bool) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/lists/{}/members.csv", MAILGUN_API_BASE, path_segment(list));
+
+        let mut form = Multipart::new();
+        form.text("upsert", if upsert { "yes" } else { "no" });
+        form.file("members", "members.csv", "text/csv", csv);
+
+        Ok(ApiRequest::post(url).endpoint("upload_list_members").multipart(form))
+    }
+
+    /// View all routes.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-routes.html)
+    pub fn get_routes(&self, params: GetRoutesParamList) -> Result<GetRoutesResponse, ClientError> {
+        self.fetch_list(self.get_routes_request(params)?)
+    }
+
+    /// Same as [`get_routes`](#method.get_routes), but returns the
+    /// response body as raw JSON.
+    pub fn get_routes_raw(&self, params: GetRoutesParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_routes_request(params)?)
+    }
+
+    fn get_routes_request(&self, params: GetRoutesParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/routes", MAILGUN_API_BASE);
+
+        Ok(ApiRequest::get(url).endpoint("get_routes").query(&params.values)?)
+    }
+
+    /// View a single route.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-routes.html)
+    pub fn get_route(&self, id: &str) -> Result<RouteResponse, ClientError> {
+        self.fetch(self.get_route_request(id)?)
+    }
+
+    /// Same as [`get_route`](#method.get_route), but returns the
+    /// response body as raw JSON.
+    pub fn get_route_raw(&self, id: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_route_request(id)?)
+    }
+
+    fn get_route_request(&self, id: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/routes/{}", MAILGUN_API_BASE, path_segment(id));
+
+        Ok(ApiRequest::get(url).endpoint("get_route"))
+    }
+
+    /// Create a new route.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-routes.html)
+    pub fn create_route(&self, params: RouteParamList) -> Result<RouteResponse, ClientError> {
+        self.fetch(self.create_route_request(params)?)
+    }
+
+    /// Same as [`create_route`](#method.create_route), but returns the
+    /// response body as raw JSON.
+    pub fn create_route_raw(&self, params: RouteParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.create_route_request(params)?)
+    }
+
+    fn create_route_request(&self, params: RouteParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/routes", MAILGUN_API_BASE);
+
+        Ok(ApiRequest::post(url).endpoint("create_route").form(&params.values)?)
+    }
+
+    /// Update an existing route. Only the given parameters are changed.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-routes.html)
+    pub fn update_route(&self, id: &str, params: RouteParamList) -> Result<UpdateRouteResponse, ClientError> {
+        self.fetch(self.update_route_request(id, params)?)
+    }
+
+    /// Same as [`update_route`](#method.update_route), but returns the
+    /// response body as raw JSON.
+    pub fn update_route_raw(&self, id: &str, params: RouteParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.update_route_request(id, params)?)
+    }
+
+    fn update_route_request(&self, id: &str, params: RouteParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/routes/{}", MAILGUN_API_BASE, path_segment(id));
+
+        Ok(ApiRequest::put(url).endpoint("update_route").form(&params.values)?)
+    }
+
+    /// Delete a route.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-routes.html)
+    pub fn delete_route(&self, id: &str) -> Result<DeleteRouteResponse, ClientError> {
+        self.fetch(self.delete_route_request(id)?)
+    }
+
+    /// Same as [`delete_route`](#method.delete_route), but returns the
+    /// response body as raw JSON.
+    pub fn delete_route_raw(&self, id: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.delete_route_request(id)?)
+    }
+
+    fn delete_route_request(&self, id: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/routes/{}", MAILGUN_API_BASE, path_segment(id));
+
+        Ok(ApiRequest::delete(url).endpoint("delete_route"))
+    }
+
+    /// Validate a single email address.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-email-validation.html#single-validation)
+    pub fn validate_address(&self, params: ValidateAddressParamList) -> Result<ValidateAddressResponse, ClientError> {
+        self.fetch(self.validate_address_request(params)?)
+    }
+
+    /// Same as [`validate_address`](#method.validate_address), but returns the
+    /// response body as raw JSON.
+    pub fn validate_address_raw(&self, params: ValidateAddressParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.validate_address_request(params)?)
+    }
+
+    fn validate_address_request(&self, params: ValidateAddressParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/address/validate", MAILGUN_API_V4_BASE);
+
+        Ok(ApiRequest::get(url).endpoint("validate_address").query(&params.values)?)
+    }
+
+    /// View all bulk validation jobs.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-email-validation.html#bulk-validation)
+    pub fn get_bulk_validations(&self, params: GetBulkValidationsParamList) -> Result<GetBulkValidationsResponse, ClientError> {
+        self.fetch_list(self.get_bulk_validations_request(params)?)
+    }
+
+    /// Same as [`get_bulk_validations`](#method.get_bulk_validations), but returns the
+    /// response body as raw JSON.
+    pub fn get_bulk_validations_raw(&self, params: GetBulkValidationsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_bulk_validations_request(params)?)
+    }
+
+    fn get_bulk_validations_request(&self, params: GetBulkValidationsParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/address/validate/bulk", MAILGUN_API_V4_BASE);
+
+        Ok(ApiRequest::get(url).endpoint("get_bulk_validations").query(&params.values)?)
+    }
+
+    /// Create a bulk validation job named `list_id` from a CSV file of
+    /// addresses.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-email-validation.html#bulk-validation)
+    pub fn create_bulk_validation(&self, list_id: &str, csv: &[u8]) -> Result<CreateBulkValidationResponse, ClientError> {
+        self.fetch(self.create_bulk_validation_request(list_id, csv)?)
+    }
+
+    /// Same as [`create_bulk_validation`](#method.create_bulk_validation), but returns the
+    /// response body as raw JSON.
+    pub fn create_bulk_validation_raw(&self, list_id: &str, csv: &[u8]) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.create_bulk_validation_request(list_id, csv)?)
+    }
+
+    fn create_bulk_validation_request(&self, list_id: &str, csv: &[u8]) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/address/validate/bulk/{}", MAILGUN_API_V4_BASE, path_segment(list_id));
+
+        let mut form = Multipart::new();
+        form.file("file", &format!("{}.csv", list_id), "text/csv", csv);
+
+        Ok(ApiRequest::post(url).endpoint("create_bulk_validation").multipart(form))
+    }
+
+    /// View the status of a bulk validation job.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-email-validation.html#bulk-validation)
+    pub fn get_bulk_validation(&self, list_id: &str) -> Result<BulkValidationJob, ClientError> {
+        self.fetch(self.get_bulk_validation_request(list_id)?)
+    }
+
+    /// Same as [`get_bulk_validation`](#method.get_bulk_validation), but returns the
+    /// response body as raw JSON.
+    pub fn get_bulk_validation_raw(&self, list_id: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_bulk_validation_request(list_id)?)
+    }
+
+    fn get_bulk_validation_request(&self, list_id: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/address/validate/bulk/{}", MAILGUN_API_V4_BASE, path_segment(list_id));
+
+        Ok(ApiRequest::get(url).endpoint("get_bulk_validation"))
+    }
+
+    /// Cancel a bulk validation job, or delete its results if it is done.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-email-validation.html#bulk-validation)
+    pub fn cancel_bulk_validation(&self, list_id: &str) -> Result<CancelBulkValidationResponse, ClientError> {
+        self.fetch(self.cancel_bulk_validation_request(list_id)?)
+    }
+
+    /// Same as [`cancel_bulk_validation`](#method.cancel_bulk_validation), but returns the
+    /// response body as raw JSON.
+    pub fn cancel_bulk_validation_raw(&self, list_id: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.cancel_bulk_validation_request(list_id)?)
+    }
+
+    fn cancel_bulk_validation_request(&self, list_id: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/address/validate/bulk/{}", MAILGUN_API_V4_BASE, path_segment(list_id));
+
+        Ok(ApiRequest::delete(url).endpoint("cancel_bulk_validation"))
+    }
+
+    /// Download the results of a finished bulk validation job.
+    ///
+    /// Returns a `ParamError::InvalidValue` if the job has no results yet.
+    pub fn download_bulk_validation(&self, job: &BulkValidationJob, format: DownloadFormat) -> Result<Vec<u8>, ClientError> {
+        let download_url = job.download_url.as_ref().ok_or_else(|| ParamError::InvalidValue(
+            "download_url".to_string(),
+            format!("bulk validation '{}' has no results to download", job.id),
+        ))?;
+
+        let url = match format {
+            DownloadFormat::Csv => &download_url.csv,
+            DownloadFormat::Json => &download_url.json,
+        };
+
+        // The download URL is pre-signed, so no authentication is sent.
+        let request = ApiRequest::get(url.to_string())
+            .unauthenticated()
+            .endpoint("download_bulk_validation");
+
+        self.send(request, |response| {
+            let mut raw = vec![];
+            response
+                .into_reader()
+                .read_to_end(&mut raw)
+                .map_err(ClientError::ReadResponse)?;
+
+            Ok(raw)
+        })
+    }
+
+    /// View all tags.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-tags.html)
+    pub fn get_tags(&self, params: GetTagsParamList) -> Result<GetTagsResponse, ClientError> {
+        self.fetch_list(self.get_tags_request(params)?)
+    }
+
+    /// Same as [`get_tags`](#method.get_tags), but returns the
+    /// response body as raw JSON.
+    pub fn get_tags_raw(&self, params: GetTagsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_tags_request(params)?)
+    }
+
+    fn get_tags_request(&self, params: GetTagsParamList) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/tags", MAILGUN_API_BASE, self.domain);
+
+        Ok(ApiRequest::get(url).endpoint("get_tags").query(&params.values)?)
+    }
+
+    /// View a single tag.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-tags.html)
+    pub fn get_tag(&self, tag: &str) -> Result<Tag, ClientError> {
+        self.fetch(self.get_tag_request(tag)?)
+    }
+
+    /// Same as [`get_tag`](#method.get_tag), but returns the
+    /// response body as raw JSON.
+    pub fn get_tag_raw(&self, tag: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_tag_request(tag)?)
+    }
+
+    fn get_tag_request(&self, tag: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/tags/{}", MAILGUN_API_BASE, self.domain, path_segment(tag));
+
+        Ok(ApiRequest::get(url).endpoint("get_tag"))
+    }
+
+    /// Update the description of a tag.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-tags.html)
+    pub fn update_tag(&self, tag: &str, description: &str) -> Result<TagMessageResponse, ClientError> {
+        self.fetch(self.update_tag_request(tag, description)?)
+    }
+
+    /// Same as [`update_tag`](#method.update_tag), but returns the
+    /// response body as raw JSON.
+    pub fn update_tag_raw(&self, tag: &str, description: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.update_tag_request(tag, description)?)
+    }
+
+    fn update_tag_request(&self, tag: &str, description: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/tags/{}", MAILGUN_API_BASE, self.domain, path_segment(tag));
+
+        let request = ApiRequest::put(url)
+            .endpoint("update_tag")
+            .form_pairs(vec![("description".to_string(), description.to_string())]);
+
+        Ok(request)
+    }
+
+    /// Delete a tag and its stats.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-tags.html)
+    pub fn delete_tag(&self, tag: &str) -> Result<TagMessageResponse, ClientError> {
+        self.fetch(self.delete_tag_request(tag)?)
+    }
+
+    /// Same as [`delete_tag`](#method.delete_tag), but returns the
+    /// response body as raw JSON.
+    pub fn delete_tag_raw(&self, tag: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.delete_tag_request(tag)?)
+    }
+
+    fn delete_tag_request(&self, tag: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/tags/{}", MAILGUN_API_BASE, self.domain, path_segment(tag));
+
+        Ok(ApiRequest::delete(url).endpoint("delete_tag"))
+    }
+
+    /// View stats for a tag.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-tags.html)
+    pub fn get_tag_stats(&self, tag: &str, params: GetStatsParamList) -> Result<GetTagStatsResponse, ClientError> {
+        self.fetch(self.get_tag_stats_request(tag, params)?)
+    }
+
+    /// Same as [`get_tag_stats`](#method.get_tag_stats), but returns the
+    /// response body as raw JSON.
+    pub fn get_tag_stats_raw(&self, tag: &str, params: GetStatsParamList) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_tag_stats_request(tag, params)?)
+    }
+
+    fn get_tag_stats_request(&self, tag: &str, params: GetStatsParamList) -> Result<ApiRequest, ClientError> {
+        params.validate()?;
+
+        let url = format!("{}/{}/tags/{}/stats", MAILGUN_API_BASE, self.domain, path_segment(tag));
+
+        Ok(ApiRequest::get(url).endpoint("get_tag_stats").query(&params.values)?)
+    }
+
+    /// View stats for a tag, aggregated by country.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-tags.html)
+    pub fn get_tag_countries(&self, tag: &str) -> Result<GetTagCountriesResponse, ClientError> {
+        self.fetch(self.get_tag_countries_request(tag)?)
+    }
+
+    /// Same as [`get_tag_countries`](#method.get_tag_countries), but returns the
+    /// response body as raw JSON.
+    pub fn get_tag_countries_raw(&self, tag: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_tag_countries_request(tag)?)
+    }
+
+    fn get_tag_countries_request(&self, tag: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/tags/{}/stats/aggregates/countries", MAILGUN_API_BASE, self.domain, path_segment(tag));
+
+        Ok(ApiRequest::get(url).endpoint("get_tag_countries"))
+    }
+
+    /// View stats for a tag, aggregated by email provider.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-tags.html)
+    pub fn get_tag_providers(&self, tag: &str) -> Result<GetTagProvidersResponse, ClientError> {
+        self.fetch(self.get_tag_providers_request(tag)?)
+    }
+
+    /// Same as [`get_tag_providers`](#method.get_tag_providers), but returns the
+    /// response body as raw JSON.
+    pub fn get_tag_providers_raw(&self, tag: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_tag_providers_request(tag)?)
+    }
+
+    fn get_tag_providers_request(&self, tag: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/tags/{}/stats/aggregates/providers", MAILGUN_API_BASE, self.domain, path_segment(tag));
+
+        Ok(ApiRequest::get(url).endpoint("get_tag_providers"))
+    }
+
+    /// View stats for a tag, aggregated by device type.
+    ///
+    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-tags.html)
+    pub fn get_tag_devices(&self, tag: &str) -> Result<GetTagDevicesResponse, ClientError> {
+        self.fetch(self.get_tag_devices_request(tag)?)
+    }
+
+    /// Same as [`get_tag_devices`](#method.get_tag_devices), but returns the
+    /// response body as raw JSON.
+    pub fn get_tag_devices_raw(&self, tag: &str) -> Result<serde_json::Value, ClientError> {
+        self.fetch(self.get_tag_devices_request(tag)?)
+    }
+
+    fn get_tag_devices_request(&self, tag: &str) -> Result<ApiRequest, ClientError> {
+        let url = format!("{}/{}/tags/{}/stats/aggregates/devices", MAILGUN_API_BASE, self.domain, path_segment(tag));
+
+        Ok(ApiRequest::get(url).endpoint("get_tag_devices"))
+    }
+
+    /// Send a request, converting transport failures and non-2xx responses
+    /// into a `ClientError`.
+    fn execute(&self, request: &ApiRequest) -> Result<(ureq::Response, ResponseMeta), ClientError> {
+        let mut call = self.agent.request(request.method.as_str(), &request.url);
+
+        call.set("User-Agent", &self.user_agent)
+            .timeout_connect(self.connect_timeout.as_millis() as u64)
+            .timeout_read(self.read_timeout.as_millis() as u64)
+            .timeout_write(self.write_timeout.as_millis() as u64);
+
+        if let Some(proxy) = &self.proxy {
+            call.set_proxy(proxy.clone());
         }
-    }
 
-    /// Make an API call from a URL.
-    ///
-    /// This will primarily be used with pagination URLs.
-    pub fn call<T>(&self, url: &str) -> Result<T, ClientError>
-    where T: serde::de::DeserializeOwned {
-        let response = ureq::get(url)
-            .auth("api", &self.api_key)
-            .call();
-
-        let status = response.status();
-        let raw = response
-            .into_string()
-            .map_err(|error| ClientError::ReadResponse(error))?;
-
-        if status != 200 {
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&raw) {
-                return Err(ClientError::ApiError(error));
-            }
+        if let Some(tls_config) = &self.tls_config {
+            call.set_tls_config(tls_config.clone());
         }
 
-        serde_json::from_str(&raw).map_err(|error| ClientError::ParseResponse(error))
-    }
+        if request.authenticated {
+            let api_key = self.api_key.read().unwrap_or_else(PoisonError::into_inner);
 
-    /// View all bounces.
-    ///
-    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#bounces)
-    pub fn get_bounces(&self, params: GetBouncesParamList) -> Result<GetBouncesResponse, ClientError> {
-        let url = format!("{}/{}/bounces", MAILGUN_API_BASE, self.domain);
+            call.auth("api", api_key.expose());
+        }
 
-        let mut request = ureq::get(&url);
-        request.auth("api", &self.api_key);
+        for (key, value) in request.query.iter() {
+            call.query(key, value);
+        }
 
-        for (key, value) in params.values.iter().map(|param| param.as_tuple()) {
-            request.query(&key, &value);
+        for (key, value) in request.headers.iter() {
+            call.set(key, value);
         }
 
-        let response = request.call();
-        let status = response.status();
+        let started = Instant::now();
 
-        let raw = response
-            .into_string()
-            .map_err(|error| ClientError::ReadResponse(error))?;
+        let response = match &request.body {
+            Body::Empty => call.call(),
+            Body::Form(form) => {
+                let form: Vec<(&str, &str)> = form.iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str()))
+                    .collect();
 
-        if status != 200 {
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&raw) {
-                return Err(ClientError::ApiError(error));
-            }
+                call.send_form(&form)
+            },
+            Body::Bytes { content_type, data } => call.set("Content-Type", content_type).send_bytes(data),
+        };
 
-            return Err(ClientError::HttpError(status, raw));
+        // ureq reports failures to send the request or read the response as
+        // responses with a made up status code.
+        if response.synthetic() {
+            let error = response.into_synthetic_error().expect("synthetic responses have an error");
+
+            return Err(ClientError::Transport(error));
         }
 
-        serde_json::from_str(&raw).map_err(|error| ClientError::ParseResponse(error))
+        let response = check_status(request.method, response)?;
+        let meta = ResponseMeta::from_response(request.method.as_str(), &response, started.elapsed());
+
+        Ok((response, meta))
     }
 
-    /// View all complaints.
-    ///
-    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#view-all-complaints)
-    pub fn get_complaints(&self, params: GetComplaintsParamList) -> Result<GetComplaintsResponse, ClientError> {
-        let url = format!("{}/{}/complaints", MAILGUN_API_BASE, self.domain);
+    /// Send a request through the middleware, and read its response with
+    /// `read`.
+    fn send<T, F>(&self, request: ApiRequest, read: F) -> Result<T, ClientError>
+    where F: FnOnce(ureq::Response) -> Result<T, ClientError> {
+        let mut request = Request(request);
 
-        let mut request = ureq::get(&url);
-        request.auth("api", &self.api_key);
+        self.middleware.before_request(&mut request);
 
-        for (key, value) in params.values.iter().map(|param| param.as_tuple()) {
-            request.query(&key, &value);
-        }
+        let call = Call::start(&request.0, &self.domain);
 
-        let response = request.call();
-        let status = response.status();
+        let result = self.execute(&request.0)
+            .and_then(|(response, meta)| Ok((read(response)?, meta)));
 
-        let raw = response
-            .into_string()
-            .map_err(|error| ClientError::ReadResponse(error))?;
+        call.finish(result.as_ref().map(|(_, meta)| meta));
 
-        if status != 200 {
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&raw) {
-                return Err(ClientError::ApiError(error));
-            }
+        match result {
+            Ok((body, meta)) => {
+                self.middleware.after_response(&request, &meta);
 
-            return Err(ClientError::HttpError(status, raw));
-        }
+                if response::is_capturing() {
+                    response::record(meta);
+                }
 
-        serde_json::from_str(&raw).map_err(|error| ClientError::ParseResponse(error))
+                Ok(body)
+            },
+            Err(error) => {
+                self.middleware.on_error(&request, &error);
+
+                Err(error)
+            },
+        }
     }
 
-    /// View all events.
-    ///
-    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-events.html)
-    pub fn get_events(&self, params: GetEventsParamList) -> Result<GetEventsResponse, ClientError> {
-        let url = format!("{}/{}/events", MAILGUN_API_BASE, self.domain);
+    /// Send a request and parse its JSON response.
+    fn fetch<T>(&self, request: ApiRequest) -> Result<T, ClientError>
+    where T: serde::de::DeserializeOwned {
+        self.send(request, |response| {
+            let raw = response.into_string().map_err(ClientError::ReadResponse)?;
 
-        let mut request = ureq::get(&url);
-        request.auth("api", &self.api_key);
+            serde_json::from_str(&raw).map_err(ClientError::ParseResponse)
+        })
+    }
 
-        for (key, value) in params.values.iter().map(|param| param.as_tuple()) {
-            request.query(&key, &value);
+    /// Send a request and parse its JSON list response, leniently if the
+    /// client is.
+    fn fetch_list<T: ItemList>(&self, request: ApiRequest) -> Result<T, ClientError> {
+        if !self.lenient {
+            return self.fetch(request);
         }
 
-        let response = request.call();
-        let status = response.status();
+        self.send(request, |response| {
+            let raw = response.into_string().map_err(ClientError::ReadResponse)?;
 
-        let raw = response
-            .into_string()
-            .map_err(|error| ClientError::ReadResponse(error))?;
+            decode_lenient(&raw).map_err(ClientError::ParseResponse)
+        })
+    }
+}
 
-        if status != 200 {
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&raw) {
-                return Err(ClientError::ApiError(error));
-            }
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("domain", &self.domain)
+            .field("lenient", &self.lenient)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("write_timeout", &self.write_timeout)
+            .field("proxy", &self.proxy.is_some())
+            .field("custom_tls", &self.tls_config.is_some())
+            .field("user_agent", &self.user_agent)
+            .field("middleware", &self.middleware)
+            .finish()
+    }
+}
 
-            return Err(ClientError::HttpError(status, raw));
-        }
+/// Builder for a [`Client`](struct.Client.html) with custom timeouts,
+/// connection pool, proxy, root certificates or user agent.
+pub struct ClientBuilder {
+    api_key: ApiKey,
+    domain: String,
+    lenient: bool,
+    agent: Option<ureq::Agent>,
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    write_timeout: Duration,
+    proxy: Option<String>,
+    root_certificates: Vec<Vec<u8>>,
+    user_agent: Option<String>,
+    middleware: MiddlewareChain,
+}
 
-        serde_json::from_str(&raw).map_err(|error| ClientError::ParseResponse(error))
+impl ClientBuilder {
+    /// Start configuring a client for `domain`, with the default timeouts and
+    /// a connection pool of its own.
+    pub fn new(api_key: &str, domain: &str) -> Self {
+        Self::with_api_key(ApiKey::new(api_key), domain)
     }
 
-    /// View all stats.
-    ///
-    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-stats.html)
-    pub fn get_stats(&self, params: GetStatsParamList) -> Result<GetStatsResponse, ClientError> {
-        let url = format!("{}/{}/stats/total", MAILGUN_API_BASE, self.domain);
+    /// Start configuring a client from an [`ApiKey`](struct.ApiKey.html),
+    /// such as one loaded from the environment.
+    pub fn with_api_key(api_key: ApiKey, domain: &str) -> Self {
+        Self {
+            api_key,
+            domain: domain.to_string(),
+            lenient: false,
+            agent: None,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_TIMEOUT,
+            write_timeout: DEFAULT_TIMEOUT,
+            proxy: None,
+            root_certificates: vec![],
+            user_agent: None,
+            middleware: MiddlewareChain::default(),
+        }
+    }
 
-        let mut request = ureq::get(&url);
-        request.auth("api", &self.api_key);
+    /// Time allowed to connect to the API. A zero duration means no limit.
+    /// Default: 10 seconds.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
 
-        for (key, value) in params.values.iter().map(|param| param.as_tuple()) {
-            request.query(&key, &value);
-        }
+        self
+    }
 
-        let response = request.call();
-        let status = response.status();
+    /// Time allowed for each read of a response. A zero duration means no
+    /// limit. Default: 60 seconds.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = timeout;
 
-        let raw = response
-            .into_string()
-            .map_err(|error| ClientError::ReadResponse(error))?;
+        self
+    }
 
-        if status != 200 {
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&raw) {
-                return Err(ClientError::ApiError(error));
-            }
+    /// Time allowed for each write of a request. A zero duration means no
+    /// limit. Default: 60 seconds.
+    pub fn write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = timeout;
 
-            return Err(ClientError::HttpError(status, raw));
-        }
+        self
+    }
+
+    /// Send requests through an agent, sharing its connection pool with
+    /// other clients and requests. By default, each client gets an agent of
+    /// its own.
+    pub fn agent(mut self, agent: ureq::Agent) -> Self {
+        self.agent = Some(agent);
 
-        serde_json::from_str(&raw).map_err(|error| ClientError::ParseResponse(error))
+        self
     }
 
-    /// View all unsubscribes.
-    ///
-    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#unsubscribes)
-    pub fn get_unsubscribes(&self, params: GetUnsubscribesParamList) -> Result<GetUnsubscribesResponse, ClientError> {
-        let url = format!("{}/{}/unsubscribes", MAILGUN_API_BASE, self.domain);
+    /// Send requests through an HTTP proxy, given as
+    /// `[user:password@]host:port`.
+    pub fn proxy(mut self, proxy: &str) -> Self {
+        self.proxy = Some(proxy.to_string());
 
-        let mut request = ureq::get(&url);
-        request.auth("api", &self.api_key);
+        self
+    }
 
-        for (key, value) in params.values.iter().map(|param| param.as_tuple()) {
-            request.query(&key, &value);
-        }
+    /// Trust a root certificate, in DER format, in addition to the default
+    /// ones.
+    pub fn add_root_certificate(mut self, der: &[u8]) -> Self {
+        self.root_certificates.push(der.to_vec());
 
-        let response = request.call();
-        let status = response.status();
+        self
+    }
 
-        let raw = response
-            .into_string()
-            .map_err(|error| ClientError::ReadResponse(error))?;
+    /// Identify the application in the `User-Agent` header. The crate's name
+    /// and version are always appended to it.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
 
-        if status != 200 {
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&raw) {
-                return Err(ClientError::ApiError(error));
-            }
+        self
+    }
 
-            return Err(ClientError::HttpError(status, raw));
-        }
+    /// Decode list responses leniently. See
+    /// [`Client::lenient`](struct.Client.html#method.lenient).
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
 
-        serde_json::from_str(&raw).map_err(|error| ClientError::ParseResponse(error))
+        self
     }
 
-    /// View all whitelist records.
-    ///
-    /// [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#view-all-whitelist-records)
-    pub fn get_whitelists(&self, params: GetWhitelistsParamList) -> Result<GetWhitelistsResponse, ClientError> {
-        let url = format!("{}/{}/whitelists", MAILGUN_API_BASE, self.domain);
+    /// Run `middleware` around every request. See
+    /// [`Client::middleware`](struct.Client.html#method.middleware).
+    pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middleware.push(middleware);
 
-        let mut request = ureq::get(&url);
-        request.auth("api", &self.api_key);
+        self
+    }
 
-        for (key, value) in params.values.iter().map(|param| param.as_tuple()) {
-            request.query(&key, &value);
+    /// Create the client.
+    ///
+    /// Returns [`BuildError::InvalidProxy`](enum.BuildError.html#variant.InvalidProxy)
+    /// if the proxy cannot be parsed, and
+    /// [`BuildError::InvalidCertificate`](enum.BuildError.html#variant.InvalidCertificate)
+    /// if one of the root certificates is not a valid DER-encoded certificate.
+    pub fn build(self) -> Result<Client, BuildError> {
+        let mut client = Client::with_api_key(self.api_key, &self.domain);
+
+        client.lenient = self.lenient;
+        client.middleware = self.middleware;
+        client.connect_timeout = self.connect_timeout;
+        client.read_timeout = self.read_timeout;
+        client.write_timeout = self.write_timeout;
+
+        if let Some(agent) = self.agent {
+            client.agent = agent;
         }
 
-        let response = request.call();
-        let status = response.status();
+        if let Some(proxy) = self.proxy {
+            client.proxy = Some(ureq::Proxy::new(proxy).map_err(BuildError::InvalidProxy)?);
+        }
 
-        let raw = response
-            .into_string()
-            .map_err(|error| ClientError::ReadResponse(error))?;
+        if !self.root_certificates.is_empty() {
+            let mut tls_config = rustls::ClientConfig::new();
+            tls_config.root_store.add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
 
-        if status != 200 {
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&raw) {
-                return Err(ClientError::ApiError(error));
+            for der in self.root_certificates {
+                tls_config.root_store
+                    .add(&rustls::Certificate(der))
+                    .map_err(BuildError::InvalidCertificate)?;
             }
 
-            return Err(ClientError::HttpError(status, raw));
+            client.tls_config = Some(Arc::new(tls_config));
+        }
+
+        if let Some(user_agent) = self.user_agent {
+            client.user_agent = format!("{} {}", user_agent, USER_AGENT);
         }
 
-        serde_json::from_str(&raw).map_err(|error| ClientError::ParseResponse(error))
+        Ok(client)
     }
+}
 
-    pub fn send_message(&self, params: SendMessageParamList<String>) -> Result<SendMessageResponse, ClientError> {
-        let url = format!("{}/{}/messages", MAILGUN_API_BASE, self.domain);
+impl fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("api_key", &self.api_key)
+            .field("domain", &self.domain)
+            .field("lenient", &self.lenient)
+            .field("agent", &self.agent)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("write_timeout", &self.write_timeout)
+            .field("proxy", &self.proxy.is_some())
+            .field("root_certificates", &self.root_certificates.len())
+            .field("user_agent", &self.user_agent)
+            .field("middleware", &self.middleware)
+            .finish()
+    }
+}
 
-        let mut request = ureq::post(&url);
-        request.auth("api", &self.api_key);
+/// Errors that might be encountered when building a client.
+#[derive(Debug, Error)]
+pub enum BuildError {
+    /// The proxy given to [`ClientBuilder::proxy`](struct.ClientBuilder.html#method.proxy)
+    /// is not a valid proxy URL.
+    #[error("Invalid proxy")]
+    InvalidProxy(#[source] ureq::Error),
+
+    /// A certificate given to
+    /// [`ClientBuilder::add_root_certificate`](struct.ClientBuilder.html#method.add_root_certificate)
+    /// could not be parsed.
+    #[error("Invalid root certificate: {0}")]
+    InvalidCertificate(webpki::Error),
+}
 
-        for param in params.values {
-            let (key, value) = param.try_as_tuple()?;
+/// The URL of a stored message, if it points to a Mailgun API host over HTTPS.
+///
+/// The URL usually comes from an event, and the API key is sent to it, so it
+/// is checked before any request is made.
+fn storage_url(storage: &EventStorage) -> Result<String, ParamError> {
+    let invalid = || ParamError::InvalidValue(
+        "storage.url".to_string(),
+        format!("'{}' is not an HTTPS URL of a mailgun.net host", storage.url),
+    );
+
+    let rest = storage.url.strip_prefix("https://").ok_or_else(invalid)?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => host,
+        _ => authority,
+    };
 
-            // TODO: If key == "attachment", set content-type to "multipart/form-data".
+    let is_valid_host = host.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'.')
+        && (host.eq_ignore_ascii_case("mailgun.net") || host.to_ascii_lowercase().ends_with(".mailgun.net"));
 
-            request.query(&key, &value);
-        }
+    if !is_valid_host {
+        return Err(invalid());
+    }
 
-        let response = request.call();
-        let status = response.status();
+    Ok(storage.url.clone())
+}
 
-        let raw = response
-            .into_string()
-            .map_err(|error| ClientError::ReadResponse(error))?;
+/// Convert a non-2xx response into an error. Some endpoints, such as creating
+/// a bulk validation job, succeed with `202 Accepted`.
+fn check_status(method: Method, response: ureq::Response) -> Result<ureq::Response, ClientError> {
+    if (200..300).contains(&response.status()) {
+        return Ok(response);
+    }
 
-        if status != 200 {
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&raw) {
-                return Err(ClientError::ApiError(error));
-            }
+    Err(match ApiError::from_response(method.as_str(), response) {
+        Ok(error) => ClientError::Api(Box::new(error)),
+        Err(error) => ClientError::ReadResponse(error),
+    })
+}
 
-            return Err(ClientError::HttpError(status, raw));
-        }
+/// Percent-encode a value for use as a single URL path segment.
+fn path_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
 
-        serde_json::from_str(&raw).map_err(|error| ClientError::ParseResponse(error))
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'@' => {
+                encoded.push(byte as char);
+            },
+            byte => encoded.push_str(&format!("%{:02X}", byte)),
+        }
     }
-}
 
-#[derive(Debug, Deserialize, Error)]
-#[serde(untagged)]
-pub enum ErrorResponse {
-    #[error("With error: {error}")]
-    WithError {
-        #[serde(alias = "Error" )]
-        error: String
-    },
-    #[error("With message: {message}")]
-    WithMessage {
-        message: String,
-    }
+    encoded
 }
 
+/// Errors that might be encountered when making a request.
 #[derive(Debug, Error)]
 pub enum ClientError {
-    #[error("Received an error message from the server: {0}")]
-    ApiError(#[from] ErrorResponse),
+    /// The API returned a non-2xx response. Responses that did not come from
+    /// the API itself, such as those of a proxy, have no error message.
+    #[error("Received an error from the server: {0}")]
+    Api(Box<ApiError>),
 
-    #[error("Received a {0} HTTP status code: {1}")]
-    HttpError(u16, String),
+    /// The request could not be sent, or the response could not be received,
+    /// such as when DNS resolution, connecting or TLS fails.
+    #[error("Failed to send request")]
+    Transport(#[source] ureq::Error),
 
-    #[error("A request parameter is invalid: {0}")]
+    #[error("A request parameter is invalid")]
     ParamError(#[from] ParamError),
 
-    #[error("Failed to parse response string: {0}")]
-    ParseResponse(serde_json::error::Error),
+    /// A 2xx response could not be parsed into the endpoint's response type.
+    #[error("Failed to parse response string")]
+    ParseResponse(#[source] serde_json::error::Error),
+
+    #[error("Failed to read response string")]
+    ReadResponse(#[source] io::Error),
+
+    /// A call passed to [`Client::with_meta`](struct.Client.html#method.with_meta)
+    /// succeeded without making a request.
+    #[error("No request was made")]
+    NoResponse,
+}
+
+impl ClientError {
+    /// The non-2xx response returned by the API, if the request reached it.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Self::Api(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Whether the same request may succeed if it is sent again later. See
+    /// [`ApiError::is_retryable`](struct.ApiError.html#method.is_retryable).
+    ///
+    /// Of the transport failures, only failed DNS lookups, failed connections
+    /// and I/O errors are retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transport(ureq::Error::DnsFailed(_))
+            | Self::Transport(ureq::Error::ConnectionFailed(_))
+            | Self::Transport(ureq::Error::Io(_)) => true,
+            Self::Transport(_) => false,
+            _ => self.api_error().is_some_and(|error| error.is_retryable()),
+        }
+    }
+
+    /// See [`ApiError::is_rate_limited`](struct.ApiError.html#method.is_rate_limited).
+    pub fn is_rate_limited(&self) -> bool {
+        self.api_error().is_some_and(|error| error.is_rate_limited())
+    }
+
+    /// See [`ApiError::is_auth_error`](struct.ApiError.html#method.is_auth_error).
+    pub fn is_auth_error(&self) -> bool {
+        self.api_error().is_some_and(|error| error.is_auth_error())
+    }
 
-    #[error("Failed to read response string: {0}")]
-    ReadResponse(io::Error),
+    /// See [`ApiError::is_not_found`](struct.ApiError.html#method.is_not_found).
+    pub fn is_not_found(&self) -> bool {
+        self.api_error().is_some_and(|error| error.is_not_found())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::endpoints::{
+        account_stats::{GetFilteredStatsParam, StatsFilter, StatsGroup},
         get_bounces::{GetBouncesParam, GetBouncesParamList},
         get_complaints::{GetComplaintsParam, GetComplaintsParamList},
         get_events::{GetEventsParam, GetEventsParamList, GetEventsResponse},
         get_stats::GetStatsParamList,
         get_unsubscribes::{GetUnsubscribesParam, GetUnsubscribesParamList},
         get_whitelists::{GetWhitelistsParam, GetWhitelistsParamList},
+        mailing_lists::{
+            GetListMembersParamList,
+            GetMailingListsParam,
+            GetMailingListsParamList,
+            ListMember,
+            ListMemberParam,
+            ListMemberParamList,
+            MailingListParam,
+            MailingListParamList,
+        },
+        routes::{Action, Filter, GetRoutesParamList, RouteParam, RouteParamList},
         send_message::{SendMessageParam, SendMessageParamList},
+        tags::{GetTagsParam, GetTagsParamList},
+        validations::{GetBulkValidationsParamList, ValidateAddressParam, ValidateAddressParamList},
     };
     use crate::param::ParamList;
     use crate::test_util::load_config;
 
     #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
     fn call() {
         let config = load_config();
         let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
@@ -327,6 +1703,221 @@ mod tests {
     }
 
     #[test]
+    fn transport_error() {
+        let client = Client::new("key", "domain.com");
+
+        let error = client.call::<serde_json::Value>("unknown://domain.com").unwrap_err();
+
+        assert!(matches!(error, ClientError::Transport(ureq::Error::UnknownScheme(_))));
+        assert!(!error.is_retryable());
+        assert!(error.api_error().is_none());
+        assert_eq!(error.to_string(), "Failed to send request");
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn with_meta_without_request() {
+        let client = Client::new("key", "domain.com");
+
+        let error = client.with_meta(|_| Ok(())).unwrap_err();
+
+        assert!(matches!(error, ClientError::NoResponse));
+    }
+
+    #[test]
+    fn retryable_transport_errors() {
+        let retryable = [
+            ureq::Error::DnsFailed("unknown host".to_string()),
+            ureq::Error::ConnectionFailed("connection refused".to_string()),
+            ureq::Error::Io(io::Error::new(io::ErrorKind::ConnectionReset, "reset")),
+        ];
+
+        for error in retryable {
+            assert!(ClientError::Transport(error).is_retryable());
+        }
+
+        let not_retryable = [
+            ureq::Error::BadUrl("url".to_string()),
+            ureq::Error::UnknownScheme("unknown".to_string()),
+            ureq::Error::TooManyRedirects,
+            ureq::Error::BadStatus,
+            ureq::Error::BadHeader,
+            ureq::Error::BadProxy,
+            ureq::Error::BadProxyCreds,
+            ureq::Error::ProxyConnect,
+            ureq::Error::InvalidProxyCreds,
+        ];
+
+        for error in not_retryable {
+            assert!(!ClientError::Transport(error).is_retryable());
+        }
+    }
+
+    #[test]
+    fn builder() {
+        let client = Client::builder("key", "domain.com")
+            .read_timeout(Duration::from_secs(5))
+            .proxy("localhost:8080")
+            .user_agent("my-app/1.0")
+            .build()
+            .unwrap();
+
+        assert_eq!(client.read_timeout, Duration::from_secs(5));
+        assert_eq!(client.connect_timeout, DEFAULT_CONNECT_TIMEOUT);
+        assert!(client.proxy.is_some());
+        assert!(client.user_agent.starts_with("my-app/1.0 mailgun-sdk-rust/"));
+        assert!(!format!("{:?}", client).contains("key"));
+
+        let error = Client::builder("key", "domain.com")
+            .add_root_certificate(b"not a certificate")
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(error, BuildError::InvalidCertificate(_)));
+    }
+
+    #[test]
+    fn debug_hides_proxy_credentials() {
+        let builder = Client::builder("key", "domain.com").proxy("user:hunter2@proxy.internal:8080");
+
+        assert!(!format!("{:?}", builder).contains("hunter2"));
+
+        let client = builder.build().unwrap();
+
+        assert!(client.proxy.is_some());
+        assert!(!format!("{:?}", client).contains("hunter2"));
+    }
+
+    #[test]
+    fn rotate_api_key() {
+        let client = Client::new("old-key", "domain.com");
+
+        client.rotate_api_key(ApiKey::new("new-key"));
+
+        assert_eq!(client.api_key.read().unwrap().expose(), "new-key");
+        assert!(!format!("{:?}", Client::builder("old-key", "domain.com")).contains("old-key"));
+    }
+
+    #[test]
+    fn storage_url() {
+        let client = Client::new("key", "domain.com");
+        let storage = |url: &str| EventStorage {
+            url: url.to_string(),
+            key: "key".to_string(),
+            extra: serde_json::Map::new(),
+        };
+
+        for url in [
+            "https://storage-us-east4.api.mailgun.net/v3/domains/domain.com/messages/key",
+            "https://api.eu.mailgun.net:443/v3/domains/domain.com/messages/key",
+        ] {
+            assert_eq!(super::storage_url(&storage(url)).unwrap(), url);
+        }
+
+        for url in [
+            "http://api.mailgun.net/v3/domains/domain.com/messages/key",
+            "https://example.com/v3/domains/domain.com/messages/key",
+            "https://api.mailgun.net@example.com/messages/key",
+            "https://api.mailgun.net.example.com/messages/key",
+            "https://evilmailgun.net/messages/key",
+        ] {
+            assert!(super::storage_url(&storage(url)).is_err(), "{}", url);
+            assert!(matches!(client.get_stored_message(&storage(url)), Err(ClientError::ParamError(_))));
+        }
+
+        assert_eq!(
+            client.stored_message_storage("a/b?c").url,
+            format!("{}/domains/domain.com/messages/a%2Fb%3Fc", MAILGUN_API_BASE),
+        );
+    }
+
+    #[test]
+    fn check_status() {
+        let response = "HTTP/1.1 202 Accepted\r\nContent-Type: application/json\r\n\r\n\
+            {\"id\": \"signups\", \"message\": \"The validation job was submitted.\"}"
+            .parse::<ureq::Response>()
+            .unwrap();
+
+        let response = super::check_status(Method::Post, response).unwrap();
+        let body: CreateBulkValidationResponse = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+
+        assert_eq!(body.id, "signups");
+
+        let response = "HTTP/1.1 404 Not Found\r\n\r\n{\"message\": \"Not found\"}"
+            .parse::<ureq::Response>()
+            .unwrap();
+
+        let error = super::check_status(Method::Delete, response).unwrap_err();
+
+        assert!(error.is_not_found());
+    }
+
+    #[test]
+    fn mailing_list_urls() {
+        let client = Client::new("key", "domain.com");
+
+        let request = client.get_mailing_list_request("dev/null@domain.com").unwrap();
+        assert_eq!(request.url, format!("{}/lists/dev%2Fnull@domain.com", MAILGUN_API_BASE));
+
+        let request = client.delete_list_member_request("dev@domain.com", "bob+news@example.com").unwrap();
+        assert_eq!(request.url, format!("{}/lists/dev@domain.com/members/bob%2Bnews@example.com", MAILGUN_API_BASE));
+
+        let request = client.get_list_members_request("dev?x#y@domain.com", GetListMembersParamList::default()).unwrap();
+        assert_eq!(request.url, format!("{}/lists/dev%3Fx%23y@domain.com/members/pages", MAILGUN_API_BASE));
+    }
+
+    #[test]
+    fn route_urls() {
+        let client = Client::new("key", "domain.com");
+
+        let request = client.get_route_request("4f3bad2335335426750048c6").unwrap();
+        assert_eq!(request.url, format!("{}/routes/4f3bad2335335426750048c6", MAILGUN_API_BASE));
+
+        let request = client.delete_route_request("../domains?x#y").unwrap();
+        assert_eq!(request.url, format!("{}/routes/..%2Fdomains%3Fx%23y", MAILGUN_API_BASE));
+    }
+
+    #[test]
+    fn bulk_validation_urls() {
+        let client = Client::new("key", "domain.com");
+
+        let request = client.get_bulk_validation_request("signups").unwrap();
+        assert_eq!(request.url, format!("{}/address/validate/bulk/signups", MAILGUN_API_V4_BASE));
+
+        let request = client.cancel_bulk_validation_request("2020/05 signups?").unwrap();
+        assert_eq!(request.url, format!("{}/address/validate/bulk/2020%2F05%20signups%3F", MAILGUN_API_V4_BASE));
+    }
+
+    #[test]
+    fn tag_urls() {
+        let client = Client::new("key", "domain.com");
+
+        let request = client.get_tag_request("2019/10 news+letter?#").unwrap();
+        assert_eq!(request.url, format!("{}/domain.com/tags/2019%2F10%20news%2Bletter%3F%23", MAILGUN_API_BASE));
+
+        let request = client.get_tag_stats_request("ünïcode", GetStatsParamList::default()).unwrap();
+        assert_eq!(request.url, format!("{}/domain.com/tags/%C3%BCn%C3%AFcode/stats", MAILGUN_API_BASE));
+    }
+
+    #[test]
+    fn custom_request() {
+        let client = Client::new("key", "domain.com");
+
+        let request = client.custom_request(Method::Get, "/tracking", &[("active", "true")]);
+
+        assert_eq!(request.method, Method::Get);
+        assert_eq!(request.url, format!("{}/domain.com/tracking", MAILGUN_API_BASE));
+        assert_eq!(request.query, vec![("active".to_string(), "true".to_string())]);
+
+        let request = client.custom_request(Method::Put, "tracking/open", &[("active", "yes")]);
+
+        assert_eq!(request.url, format!("{}/domain.com/tracking/open", MAILGUN_API_BASE));
+        assert!(request.query.is_empty());
+        assert!(matches!(request.body, Body::Form(ref form) if form.len() == 1));
+    }
+
+    #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
     fn get_bounces() {
         let config = load_config();
         let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
@@ -341,6 +1932,7 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
     fn get_complains() {
         let config = load_config();
         let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
@@ -355,6 +1947,7 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
     fn get_events() {
         let config = load_config();
         let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
@@ -367,6 +1960,7 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
     fn get_stats() {
         let config = load_config();
         let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
@@ -377,6 +1971,42 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
+    fn account_stats() {
+        let config = load_config();
+        let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
+
+        let _totals = client.get_account_stats(GetStatsParamList::default()).unwrap();
+
+        let params = GetFilteredStatsParamList::default()
+            .add(GetFilteredStatsParam::Filter(StatsFilter::Domain(&config.mailgun_domain)))
+            .add(GetFilteredStatsParam::Group(StatsGroup::Tag));
+        let _filtered = client.get_filtered_stats(params).unwrap();
+
+        let _countries = client.get_domain_countries().unwrap();
+        let _providers = client.get_domain_providers().unwrap();
+        let _devices = client.get_domain_devices().unwrap();
+    }
+
+    #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
+    fn get_stored_message() {
+        let config = load_config();
+        let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
+
+        let params = GetEventsParamList::default()
+            .add(GetEventsParam::Event("accepted"))
+            .add(GetEventsParam::Limit(1));
+        let events = client.get_events(params).unwrap();
+
+        if let Some(storage) = events.items.first().and_then(|event| event.storage.as_ref()) {
+            let _message = client.get_stored_message(storage).unwrap();
+            let _mime = client.get_stored_message_mime(storage).unwrap();
+        }
+    }
+
+    #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
     fn get_unsubscribes() {
         let config = load_config();
         let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
@@ -391,6 +2021,7 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
     fn get_whitelists() {
         let config = load_config();
         let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
@@ -405,6 +2036,93 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
+    fn mailing_lists() {
+        let config = load_config();
+        let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
+
+        let params = GetMailingListsParamList::default()
+            .add(GetMailingListsParam::Limit(1));
+        let _lists = client.get_mailing_lists(params).unwrap();
+
+        let address = format!("sdk-test@{}", &config.mailgun_domain);
+        let params = MailingListParamList::default()
+            .add(MailingListParam::Address(&address))
+            .add(MailingListParam::Name("SDK Test"));
+        client.create_mailing_list(params).unwrap();
+
+        let params = ListMemberParamList::default()
+            .add(ListMemberParam::Address("Bob <bob@example.com>"))
+            .add(ListMemberParam::Upsert(true));
+        client.add_list_member(&address, params).unwrap();
+
+        let members = vec![ListMember::new("alice@example.com")];
+        client.add_list_members(&address, &members, true).unwrap();
+
+        let _members = client.get_list_members(&address, GetListMembersParamList::default()).unwrap();
+
+        client.delete_list_member(&address, "bob@example.com").unwrap();
+        client.delete_mailing_list(&address).unwrap();
+    }
+
+    #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
+    fn routes() {
+        let config = load_config();
+        let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
+
+        let _routes = client.get_routes(GetRoutesParamList::default()).unwrap();
+
+        let recipient = format!("sdk-test@{}", &config.mailgun_domain);
+        let params = RouteParamList::default()
+            .add(RouteParam::Description("SDK Test"))
+            .add(RouteParam::Expression(Filter::match_recipient(&recipient)))
+            .add(RouteParam::Action(Action::store(None)))
+            .add(RouteParam::Action(Action::stop()));
+        let created = client.create_route(params).unwrap();
+
+        let params = RouteParamList::default()
+            .add(RouteParam::Priority(10));
+        client.update_route(&created.route.id, params).unwrap();
+
+        let _route = client.get_route(&created.route.id).unwrap();
+        client.delete_route(&created.route.id).unwrap();
+    }
+
+    #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
+    fn tags() {
+        let config = load_config();
+        let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
+
+        let params = GetTagsParamList::default()
+            .add(GetTagsParam::Limit(1));
+        let tags = client.get_tags(params).unwrap();
+
+        if let Some(tag) = tags.items.first() {
+            let _tag = client.get_tag(&tag.tag).unwrap();
+            let _stats = client.get_tag_stats(&tag.tag, GetStatsParamList::default()).unwrap();
+            let _countries = client.get_tag_countries(&tag.tag).unwrap();
+            let _providers = client.get_tag_providers(&tag.tag).unwrap();
+            let _devices = client.get_tag_devices(&tag.tag).unwrap();
+        }
+    }
+
+    #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
+    fn validate_address() {
+        let config = load_config();
+        let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
+
+        let params = ValidateAddressParamList::default()
+            .add(ValidateAddressParam::Address("rrider@pfgcapital.com"));
+        let _validation = client.validate_address(params).unwrap();
+
+        let _jobs = client.get_bulk_validations(GetBulkValidationsParamList::default()).unwrap();
+    }
+
+    #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
     fn send_message() {
         let config = load_config();
         let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
@@ -418,4 +2136,20 @@ mod tests {
 
         client.send_message(params).unwrap();
     }
+
+    #[test]
+    #[ignore = "calls the Mailgun API with the account in .test.env"]
+    fn send_mime() {
+        let config = load_config();
+        let client = Client::new(&config.mailgun_api_key, &config.mailgun_domain);
+
+        let mime = format!(
+            "From: Test <test@{}>\r\nTo: rrider@pfgcapital.com\r\nSubject: Test\r\n\r\ntest message\r\n",
+            &config.mailgun_domain,
+        );
+        let params = SendMessageParamList::default()
+            .add(SendMessageParam::OTestMode(true));
+
+        client.send_mime("rrider@pfgcapital.com", mime.as_bytes(), params).unwrap();
+    }
 }
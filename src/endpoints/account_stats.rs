@@ -0,0 +1,251 @@
+//! Request and response module for fetching stats across all domains of an
+//! account.
+//!
+//! Account-wide totals take the same parameters, and return the same items, as
+//! [`get_stats`](../get_stats/index.html). Filtered stats can additionally be
+//! narrowed down to a domain, tag, subaccount or IP pool, and grouped by any of
+//! those.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     ParamList,
+//! #     account_stats::{GetFilteredStatsParam, GetFilteredStatsParamList, StatsFilter, StatsGroup},
+//! #     get_stats::GetStatsParamList,
+//! # };
+//! # let client = Client::new("", "");
+//! let totals = client.get_account_stats(GetStatsParamList::default()).unwrap();
+//!
+//! let request = GetFilteredStatsParamList::default()
+//!     .add(GetFilteredStatsParam::Filter(StatsFilter::Tag("newsletter")))
+//!     .add(GetFilteredStatsParam::Group(StatsGroup::Domain));
+//!
+//! let by_domain = client.get_filtered_stats(request).unwrap();
+//! ```
+//!
+//! [API Documentation](https://documentation.mailgun.com/en/latest/api-stats.html)
+
+use crate::get_stats::{
+    date_value,
+    Duration,
+    GetStatsParam,
+    GetStatsParamList,
+    Resolution,
+    StatAccepted,
+    StatClicked,
+    StatDelivered,
+    StatEvent,
+    StatFailed,
+    StatOpened,
+    StatTotal,
+};
+use crate::{Param, ParamError, ParamList};
+
+use std::fmt;
+
+/// A condition that filtered stats must match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsFilter<'a> {
+    Domain(&'a str),
+    Tag(&'a str),
+    Subaccount(&'a str),
+    IpPool(&'a str),
+}
+
+impl<'a> fmt::Display for StatsFilter<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Domain(v) => write!(f, "domain:{}", v),
+            Self::Tag(v) => write!(f, "tag:{}", v),
+            Self::Subaccount(v) => write!(f, "subaccount:{}", v),
+            Self::IpPool(v) => write!(f, "ip_pool:{}", v),
+        }
+    }
+}
+
+/// A field that filtered stats are grouped by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsGroup {
+    Domain,
+    Tag,
+    Subaccount,
+    IpPool,
+}
+
+impl fmt::Display for StatsGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Domain => write!(f, "domain"),
+            Self::Tag => write!(f, "tag"),
+            Self::Subaccount => write!(f, "subaccount"),
+            Self::IpPool => write!(f, "ip_pool"),
+        }
+    }
+}
+
+//- Request
+
+/// A parameter for fetching filtered stats.
+#[derive(Debug)]
+pub enum GetFilteredStatsParam<'a> {
+    /// Period of time with resoluton encoded. Cannot be combined with a start
+    /// date, which it would overwrite.
+    Duration(Duration),
+    /// The ending date. Should be in RFC 2822 or unix epoch format.
+    /// Default: current time.
+    End(&'a str),
+    /// The type of the event. You can post multiple event values.
+    Event(StatEvent),
+    /// Only count events matching this filter. You can post multiple filter
+    /// values.
+    Filter(StatsFilter<'a>),
+    /// Split the counts by this field. You can post multiple group values.
+    Group(StatsGroup),
+    /// Size of the time buckets. Default: day.
+    Resolution(Resolution),
+    /// The starting time. Should be in RFC 2822 or unix epoch format.
+    /// Default: 7 days from the current time.
+    Start(&'a str),
+}
+
+impl<'a> GetFilteredStatsParam<'a> {
+    /// The equivalent parameter of the unfiltered stats endpoints, if any.
+    fn as_stats_param(&self) -> Option<GetStatsParam<'a>> {
+        match self {
+            Self::Duration(v) => Some(GetStatsParam::Duration(*v)),
+            Self::End(v) => Some(GetStatsParam::End(v)),
+            Self::Event(v) => Some(GetStatsParam::Event(*v)),
+            Self::Resolution(v) => Some(GetStatsParam::Resolution(*v)),
+            Self::Start(v) => Some(GetStatsParam::Start(v)),
+            Self::Filter(_) | Self::Group(_) => None,
+        }
+    }
+}
+
+impl<'a> Param for GetFilteredStatsParam<'a> {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Duration(v) => ("duration".to_string(), v.to_string()),
+            Self::End(v) => ("end".to_string(), date_value("end", v)?),
+            Self::Event(v) => ("event".to_string(), v.to_string()),
+            Self::Filter(v) => ("filter".to_string(), v.to_string()),
+            Self::Group(v) => ("group".to_string(), v.to_string()),
+            Self::Resolution(v) => ("resolution".to_string(), v.to_string()),
+            Self::Start(v) => ("start".to_string(), date_value("start", v)?),
+        })
+    }
+}
+
+/// List of parameters for fetching filtered stats.
+#[derive(Debug)]
+pub struct GetFilteredStatsParamList<'a> {
+    pub values: Vec<GetFilteredStatsParam<'a>>,
+}
+
+impl<'a> GetFilteredStatsParamList<'a> {
+    /// Check the parameters against the API's rules, which are the same as
+    /// for [`GetStatsParamList`](../get_stats/struct.GetStatsParamList.html).
+    /// This is done by the client before each request.
+    pub fn validate(&self) -> Result<(), ParamError> {
+        GetStatsParamList {
+            values: self.values.iter().filter_map(GetFilteredStatsParam::as_stats_param).collect(),
+        }.validate()
+    }
+}
+
+impl<'a> Default for GetFilteredStatsParamList<'a> {
+    fn default() -> Self {
+        Self {
+            values: vec![
+                GetFilteredStatsParam::Event(StatEvent::Accepted),
+                GetFilteredStatsParam::Event(StatEvent::Delivered),
+                GetFilteredStatsParam::Event(StatEvent::Failed),
+            ],
+        }
+    }
+}
+
+impl<'a> ParamList for GetFilteredStatsParamList<'a> {
+    type ParamType = GetFilteredStatsParam<'a>;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+//- Response
+
+/// Response returned by get filtered stats endpoint.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetFilteredStatsResponse {
+    pub end: String,
+    pub resolution: String,
+    pub start: String,
+    pub stats: Vec<FilteredStatItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A single item found in [`GetFilteredStatsResponse`](struct.GetFilteredStatsResponse.html).
+///
+/// Only the events that were requested, and the fields that were grouped by,
+/// are present.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FilteredStatItem {
+    pub time: Option<String>,
+    pub domain: Option<String>,
+    pub tag: Option<String>,
+    pub subaccount: Option<String>,
+    pub ip_pool: Option<String>,
+    pub accepted: Option<StatAccepted>,
+    pub delivered: Option<StatDelivered>,
+    pub failed: Option<StatFailed>,
+    pub opened: Option<StatOpened>,
+    pub clicked: Option<StatClicked>,
+    pub unsubscribed: Option<StatTotal>,
+    pub complained: Option<StatTotal>,
+    pub stored: Option<StatTotal>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_params() {
+        let params = GetFilteredStatsParamList::default()
+            .add(GetFilteredStatsParam::Filter(StatsFilter::IpPool("pool-1")))
+            .add(GetFilteredStatsParam::Group(StatsGroup::Subaccount))
+            .add(GetFilteredStatsParam::Resolution(Resolution::Month));
+
+        let pairs: Vec<_> = params.values.iter().skip(3).map(|param| param.as_tuple()).collect();
+
+        assert_eq!(pairs, vec![
+            ("filter".to_string(), "ip_pool:pool-1".to_string()),
+            ("group".to_string(), "subaccount".to_string()),
+            ("resolution".to_string(), "month".to_string()),
+        ]);
+        assert!(params.validate().is_ok());
+
+        let params = GetFilteredStatsParamList::default()
+            .add(GetFilteredStatsParam::Start("tomorrow"));
+
+        assert!(params.validate().is_err());
+
+        assert_eq!(
+            GetFilteredStatsParam::Duration("24h".parse().unwrap()).as_tuple(),
+            ("duration".to_string(), "24h".to_string()),
+        );
+        assert_eq!(
+            GetFilteredStatsParam::End("1571184000").as_tuple(),
+            ("end".to_string(), "1571184000".to_string()),
+        );
+        assert!(GetFilteredStatsParam::Start("tomorrow").try_as_tuple().is_err());
+    }
+}
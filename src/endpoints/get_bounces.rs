@@ -17,7 +17,7 @@
 //!
 //! [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#bounces)
 
-use crate::{Paging, Param, ParamError, ParamList};
+use crate::{InvalidItem, ItemList, Paging, Param, ParamError, ParamList};
 
 //- Request
 
@@ -37,19 +37,11 @@ impl Param for GetBouncesParam {
 }
 
 /// List of parameters for fetching bounces for a domain.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetBouncesParamList {
     pub values: Vec<GetBouncesParam>,
 }
 
-impl Default for GetBouncesParamList {
-    fn default() -> Self {
-        Self {
-            values: vec![],
-        }
-    }
-}
-
 impl ParamList for GetBouncesParamList {
     type ParamType = GetBouncesParam;
 
@@ -67,6 +59,18 @@ impl ParamList for GetBouncesParamList {
 pub struct GetBouncesResponse {
     pub items: Vec<BounceItem>,
     pub paging: Paging,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetBouncesResponse {
+    type Item = BounceItem;
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
 }
 
 /// A single item found in [`GetBouncesResponse`](struct.GetBouncesResponse.html).
@@ -76,4 +80,6 @@ pub struct BounceItem {
     pub code: String,
     pub error: String,
     pub created_at: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
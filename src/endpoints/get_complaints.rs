@@ -17,7 +17,7 @@
 //!
 //! [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#view-all-complaints)
 
-use crate::{Paging, Param, ParamError, ParamList};
+use crate::{InvalidItem, ItemList, Paging, Param, ParamError, ParamList};
 
 //- Request
 
@@ -37,19 +37,11 @@ impl Param for GetComplaintsParam {
 }
 
 /// List of parameters for fetching complaints for a domain.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetComplaintsParamList {
     pub values: Vec<GetComplaintsParam>,
 }
 
-impl Default for GetComplaintsParamList {
-    fn default() -> Self {
-        Self {
-            values: vec![],
-        }
-    }
-}
-
 impl ParamList for GetComplaintsParamList {
     type ParamType = GetComplaintsParam;
 
@@ -67,6 +59,18 @@ impl ParamList for GetComplaintsParamList {
 pub struct GetComplaintsResponse {
     pub items: Vec<ComplaintItem>,
     pub paging: Paging,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetComplaintsResponse {
+    type Item = ComplaintItem;
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
 }
 
 /// A single item found in [`GetComplaintsResponse`](struct.GetComplaintsResponse.html).
@@ -75,4 +79,6 @@ pub struct ComplaintItem {
     pub address: String,
     pub tag: String,
     pub created_at: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
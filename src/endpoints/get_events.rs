@@ -17,7 +17,7 @@
 //!
 //! [API Documentation](https://documentation.mailgun.com/en/latest/api-events.html#events)
 
-use crate::{Paging, Param, ParamError, ParamList};
+use crate::{InvalidItem, ItemList, Paging, Param, ParamError, ParamList};
 
 //- Request
 
@@ -122,6 +122,18 @@ impl<'a> ParamList for GetEventsParamList<'a> {
 pub struct GetEventsResponse {
     pub items: Vec<EventItem>,
     pub paging: Paging,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetEventsResponse {
+    type Item = EventItem;
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
 }
 
 /// A single item found in [`GetEventsResponse`](struct.GetEventsResponse.html).
@@ -148,6 +160,8 @@ pub struct EventItem {
     pub ip: Option<String>,
     pub client_info: Option<EventClientInfo>,
     // TODO: Figure out what the `user-variables` field looks like.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single envelope item found in [`EventItem`](struct.EventItem.html).
@@ -156,6 +170,8 @@ pub struct EventEnvelope {
     pub targets: String,
     pub transport: String,
     pub sender: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single event flag item found in [`EventItem`](struct.EventItem.html).
@@ -167,6 +183,8 @@ pub struct EventFlags {
     pub is_routed: Option<bool>,
     pub is_system_test: Option<bool>,
     pub is_test_mode: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single event reject item found in [`EventItem`](struct.EventItem.html).
@@ -174,6 +192,8 @@ pub struct EventFlags {
 pub struct EventReject {
     pub reason: Option<String>,
     pub description: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single event delivery status item found in [`EventItem`](struct.EventItem.html).
@@ -189,6 +209,8 @@ pub struct EventDeliveryStatus {
     pub attempt_no: Option<usize>,
     pub message: Option<String>,
     pub certificate_verified: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single event message item found in [`EventItem`](struct.EventItem.html).
@@ -198,6 +220,8 @@ pub struct EventMessage {
     pub attachments: Option<Vec<EventMessageAttachment>>,
     pub recipients: Option<Vec<String>>,
     pub size: Option<i64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single event message header item found in [`EventMessage`](struct.EventMessage.html).
@@ -208,6 +232,8 @@ pub struct EventMessageHeader {
     pub message_id: Option<String>,
     pub from: Option<String>,
     pub subject: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single event message attachment item found in [`EventMessage`](struct.EventMessage.html).
@@ -217,6 +243,8 @@ pub struct EventMessageAttachment {
     pub size: Option<i64>,
     pub content_type: Option<String>,
     pub filename: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single event storage item found in [`EventItem`](struct.EventItem.html).
@@ -224,6 +252,8 @@ pub struct EventMessageAttachment {
 pub struct EventStorage {
     pub url: String,
     pub key: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single event geolocation item found in [`EventItem`](struct.EventItem.html).
@@ -232,6 +262,8 @@ pub struct EventGeolocation {
     pub country: String,
     pub region: String,
     pub city: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single event client info item found in [`EventItem`](struct.EventItem.html).
@@ -243,4 +275,6 @@ pub struct EventClientInfo {
     pub device_type: Option<String>,
     pub client_name: Option<String>,
     pub user_agent: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -1,39 +1,211 @@
 //! Request and response module for fetching stats for a domain.
 //!
+//! Stats are counted per time bucket, or as totals per country, email
+//! provider or device type with the aggregate endpoints.
+//!
 //! ### Example
 //!
 //! ```no_run
 //! # use mailgun_sdk::{
 //! #     Client,
 //! #     ParamList,
-//! #     get_stats::{GetStatsParam, GetStatsParamList},
+//! #     get_stats::{Duration, GetStatsParam, GetStatsParamList, Resolution, StatEvent},
 //! # };
 //! # let client = Client::new("", "");
 //! let request = GetStatsParamList::default()
-//!     .add(GetStatsParam::Resolution("month"));
+//!     .add(GetStatsParam::Event(StatEvent::Opened))
+//!     .add(GetStatsParam::Duration("6m".parse().unwrap()))
+//!     .add(GetStatsParam::Resolution(Resolution::Month));
 //!
 //! let stats = client.get_stats(request).unwrap();
+//! let providers = client.get_domain_providers().unwrap();
 //! ```
 //!
 //! [API Documentation](https://documentation.mailgun.com/en/latest/api-stats.html)
 
 use crate::{Param, ParamError, ParamList};
+use chrono::{DateTime, TimeZone, Utc};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A type of event that stats are counted for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatEvent {
+    Accepted,
+    Delivered,
+    Failed,
+    Opened,
+    Clicked,
+    Unsubscribed,
+    Complained,
+    Stored,
+}
+
+impl fmt::Display for StatEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Accepted => write!(f, "accepted"),
+            Self::Delivered => write!(f, "delivered"),
+            Self::Failed => write!(f, "failed"),
+            Self::Opened => write!(f, "opened"),
+            Self::Clicked => write!(f, "clicked"),
+            Self::Unsubscribed => write!(f, "unsubscribed"),
+            Self::Complained => write!(f, "complained"),
+            Self::Stored => write!(f, "stored"),
+        }
+    }
+}
+
+/// Size of the time buckets that stats are grouped into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Resolution {
+    Hour,
+    Day,
+    Month,
+}
+
+impl FromStr for Resolution {
+    type Err = ParamError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hour" => Ok(Self::Hour),
+            "day" => Ok(Self::Day),
+            "month" => Ok(Self::Month),
+            _ => Err(ParamError::InvalidValue(
+                "resolution".to_string(),
+                format!("expected hour, day or month, got '{}'", s),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Hour => write!(f, "hour"),
+            Self::Day => write!(f, "day"),
+            Self::Month => write!(f, "month"),
+        }
+    }
+}
+
+/// Period of time to fetch stats for, counted back from the end date.
+///
+/// ```
+/// # use mailgun_sdk::get_stats::{Duration, Resolution};
+/// let duration: Duration = "7d".parse().unwrap();
+///
+/// assert_eq!(duration, Duration::new(7, Resolution::Day).unwrap());
+/// assert_eq!(duration.to_string(), "7d");
+/// assert!("7w".parse::<Duration>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Duration {
+    amount: u32,
+    resolution: Resolution,
+}
+
+impl Duration {
+    /// Create a duration of `amount` hours, days or months. The amount must
+    /// not be zero.
+    pub fn new(amount: u32, resolution: Resolution) -> Result<Self, ParamError> {
+        if amount == 0 {
+            return Err(ParamError::InvalidValue(
+                "duration".to_string(),
+                "must be at least 1".to_string(),
+            ));
+        }
+
+        Ok(Self { amount, resolution })
+    }
+
+    /// Number of hours, days or months in the duration.
+    pub fn amount(&self) -> u32 {
+        self.amount
+    }
+
+    /// Unit of the duration.
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+}
+
+impl FromStr for Duration {
+    type Err = ParamError;
+
+    /// Parse a duration in `[0-9]+[hdm]` format, such as `24h`, `7d` or `1m`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParamError::InvalidValue(
+            "duration".to_string(),
+            format!("expected [0-9]+h, [0-9]+d or [0-9]+m, got '{}'", s),
+        );
+
+        let resolution = match s.chars().last() {
+            Some('h') => Resolution::Hour,
+            Some('d') => Resolution::Day,
+            Some('m') => Resolution::Month,
+            _ => return Err(invalid()),
+        };
+
+        let amount = &s[..s.len() - 1];
+
+        if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        Self::new(amount.parse().map_err(|_| invalid())?, resolution)
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unit = match self.resolution {
+            Resolution::Hour => 'h',
+            Resolution::Day => 'd',
+            Resolution::Month => 'm',
+        };
+
+        write!(f, "{}{}", self.amount, unit)
+    }
+}
+
+/// Parse a stats date in RFC 2822 or unix epoch format.
+///
+/// The API returns dates with a `UTC` zone, which is not one of the zone
+/// names allowed by RFC 2822, so it is accepted here as well.
+pub(crate) fn parse_time(time: &str) -> Option<DateTime<Utc>> {
+    if !time.is_empty() && time.bytes().all(|b| b.is_ascii_digit()) {
+        return time.parse().ok().and_then(|seconds| Utc.timestamp_opt(seconds, 0).single());
+    }
+
+    let normalized = match time.strip_suffix(" UTC") {
+        Some(time) => format!("{} +0000", time),
+        None => time.to_string(),
+    };
+
+    DateTime::parse_from_rfc2822(&normalized)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
 
 //- Request
 
 /// A parameter for fetching stats for a domain.
 #[derive(Debug)]
 pub enum GetStatsParam<'a> {
-    /// Period of time with resoluton encoded. If provided, overwrites the
-    /// start date.
-    Duration(&'a str),
+    /// Period of time with resoluton encoded. Cannot be combined with a start
+    /// date, which it would overwrite.
+    Duration(Duration),
     /// The ending date. Should be in RFC 2822 or unix epoch format.
     /// Default: current time.
     End(&'a str),
-    /// The type of the event.
-    Event(&'a str),
-    /// Can be either hour, day or month. Default: day.
-    Resolution(&'a str),
+    /// The type of the event. You can post multiple event values.
+    Event(StatEvent),
+    /// Size of the time buckets. Default: day.
+    Resolution(Resolution),
     /// The starting time. Should be in RFC 2822 or unix epoch format.
     /// Default: 7 days from the current time.
     Start(&'a str),
@@ -43,14 +215,24 @@ impl<'a> Param for GetStatsParam<'a> {
     fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
         Ok(match self {
             Self::Duration(v) => ("duration".to_string(), v.to_string()),
-            Self::End(v) => ("end".to_string(), v.to_string()),
+            Self::End(v) => ("end".to_string(), date_value("end", v)?),
             Self::Event(v) => ("event".to_string(), v.to_string()),
             Self::Resolution(v) => ("resolution".to_string(), v.to_string()),
-            Self::Start(v) => ("start".to_string(), v.to_string()),
+            Self::Start(v) => ("start".to_string(), date_value("start", v)?),
         })
     }
 }
 
+pub(crate) fn date_value(key: &str, value: &str) -> Result<String, ParamError> {
+    match parse_time(value) {
+        Some(_) => Ok(value.to_string()),
+        None => Err(ParamError::InvalidValue(
+            key.to_string(),
+            format!("'{}' is not an RFC 2822 date or unix epoch", value),
+        )),
+    }
+}
+
 /// List of parameters for fetching stats for a domain.
 #[derive(Debug)]
 pub struct GetStatsParamList<'a> {
@@ -61,14 +243,58 @@ impl<'a> Default for GetStatsParamList<'a> {
     fn default() -> Self {
         Self {
             values: vec![
-                GetStatsParam::Event("accepted"),
-                GetStatsParam::Event("delivered"),
-                GetStatsParam::Event("failed"),
+                GetStatsParam::Event(StatEvent::Accepted),
+                GetStatsParam::Event(StatEvent::Delivered),
+                GetStatsParam::Event(StatEvent::Failed),
             ],
         }
     }
 }
 
+impl<'a> GetStatsParamList<'a> {
+    /// Check the parameters against the API's rules. This is done by the
+    /// client before each request.
+    pub fn validate(&self) -> Result<(), ParamError> {
+        let mut errors = vec![];
+        let mut duration = None;
+        let mut start = None;
+        let mut end = None;
+
+        for param in self.values.iter() {
+            if let Err(error) = param.try_as_tuple() {
+                errors.push(error);
+                continue;
+            }
+
+            match param {
+                GetStatsParam::Duration(v) => duration = Some(v),
+                GetStatsParam::Start(v) => start = parse_time(v),
+                GetStatsParam::End(v) => end = parse_time(v),
+                _ => {},
+            }
+        }
+
+        if let (Some(duration), Some(_)) = (duration, start) {
+            errors.push(ParamError::InvalidValue(
+                "start".to_string(),
+                format!("cannot be combined with duration '{}', which overwrites it", duration),
+            ));
+        }
+
+        if let (Some(start), Some(end)) = (start, end) {
+            if end < start {
+                errors.push(ParamError::InvalidValue("end".to_string(), "must not be before start".to_string()));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ParamError::Validation(errors))
+        }
+    }
+}
+
 impl<'a> ParamList for GetStatsParamList<'a> {
     type ParamType = GetStatsParam<'a>;
 
@@ -82,60 +308,200 @@ impl<'a> ParamList for GetStatsParamList<'a> {
 //- Response
 
 /// Response returned by get stats endpoint.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GetStatsResponse {
-    end: String,
-    resolution: String,
-    start: String,
-    stats: Vec<StatItem>,
+    pub end: String,
+    pub resolution: String,
+    pub start: String,
+    pub stats: Vec<StatItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single item found in [`GetStatsResponse`](struct.GetStatsResponse.html).
-#[derive(Debug, Deserialize, Serialize)]
+///
+/// Only the events that were requested are present.
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct StatItem {
-    time: String,
-    accepted: StatAccepted,
-    delivered: StatDelivered,
-    failed: StatFailed,
+    pub time: String,
+    pub accepted: Option<StatAccepted>,
+    pub delivered: Option<StatDelivered>,
+    pub failed: Option<StatFailed>,
+    pub opened: Option<StatOpened>,
+    pub clicked: Option<StatClicked>,
+    pub unsubscribed: Option<StatTotal>,
+    pub complained: Option<StatTotal>,
+    pub stored: Option<StatTotal>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single accepted item found in [`StatItem`](struct.StatItem.html).
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct StatAccepted {
-    outgoing: i64,
-    incoming: i64,
-    total: i64,
+    pub outgoing: i64,
+    pub incoming: i64,
+    pub total: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single delivered item found in [`StatItem`](struct.StatItem.html).
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct StatDelivered {
-    smtp: i64,
-    http: i64,
-    total: i64,
+    pub smtp: i64,
+    pub http: i64,
+    pub total: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single failed item found in [`StatItem`](struct.StatItem.html).
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct StatFailed {
-    permanent: StatFailedPermanent,
-    temporary: StatFailedTemporary,
+    pub permanent: StatFailedPermanent,
+    pub temporary: StatFailedTemporary,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single failed permanent item found in [`StatFailed`](struct.StatFailed.html).
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct StatFailedPermanent {
-    bounce: i64,
-    delayed_bounce: i64,
-    suppress_bounce: i64,
-    suppress_unsubscribe: i64,
-    suppress_complaint: i64,
-    total: i64,
+    pub bounce: i64,
+    pub delayed_bounce: i64,
+    pub suppress_bounce: i64,
+    pub suppress_unsubscribe: i64,
+    pub suppress_complaint: i64,
+    pub total: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A single failed temporary item found in [`StatFailed`](struct.StatFailed.html).
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct StatFailedTemporary {
-    espblock: i64,
+    pub espblock: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A single opened item found in [`StatItem`](struct.StatItem.html).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StatOpened {
+    pub total: i64,
+    pub unique: Option<i64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A single clicked item found in [`StatItem`](struct.StatItem.html).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StatClicked {
+    pub total: i64,
+    pub unique: Option<i64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A single unsubscribed, complained or stored item found in
+/// [`StatItem`](struct.StatItem.html).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StatTotal {
+    pub total: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Counts of each event for a single country, email provider or device type,
+/// found in the aggregate stats responses.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AggregateCounts {
+    pub accepted: i64,
+    pub delivered: i64,
+    pub opened: i64,
+    pub unique_opened: i64,
+    pub clicked: i64,
+    pub unique_clicked: i64,
+    pub unsubscribed: i64,
+    pub complained: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by get domain countries endpoint, keyed by country code.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetDomainCountriesResponse {
+    #[serde(alias = "countries")]
+    pub country: HashMap<String, AggregateCounts>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by get domain providers endpoint, keyed by email
+/// provider domain.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetDomainProvidersResponse {
+    #[serde(alias = "providers")]
+    pub provider: HashMap<String, AggregateCounts>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by get domain devices endpoint, keyed by device type.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetDomainDevicesResponse {
+    #[serde(alias = "devices")]
+    pub device: HashMap<String, AggregateCounts>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_stat_item() {
+        let raw = r#"{
+            "time": "Mon, 14 Oct 2019 00:00:00 UTC",
+            "accepted": { "outgoing": 10, "incoming": 0, "total": 10 },
+            "opened": { "total": 4, "unique": 3 },
+            "clicked": { "total": 2 },
+            "complained": { "total": 1 }
+        }"#;
+
+        let item: StatItem = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(item.accepted.unwrap().total, 10);
+        assert_eq!(item.opened.unwrap().unique, Some(3));
+        assert_eq!(item.clicked.unwrap().unique, None);
+        assert_eq!(item.complained.unwrap().total, 1);
+        assert!(item.delivered.is_none());
+    }
+
+    #[test]
+    fn validate() {
+        let params = GetStatsParamList::default()
+            .add(GetStatsParam::Resolution(Resolution::Hour))
+            .add(GetStatsParam::Start("Mon, 14 Oct 2019 00:00:00 UTC"))
+            .add(GetStatsParam::End("1571184000"));
+
+        assert!(params.validate().is_ok());
+
+        let params = GetStatsParamList::default()
+            .add(GetStatsParam::Duration("24h".parse().unwrap()))
+            .add(GetStatsParam::Start("1571184000"))
+            .add(GetStatsParam::End("yesterday"));
+
+        match params.validate() {
+            Err(ParamError::Validation(errors)) => assert_eq!(errors.len(), 2),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        assert!("0d".parse::<Duration>().is_err());
+        assert_eq!("3m".parse::<Duration>().unwrap().resolution(), Resolution::Month);
+    }
 }
@@ -0,0 +1,84 @@
+//! Response module for retrieving and resending stored messages.
+//!
+//! Mailgun keeps a copy of sent and received messages for a few days. Events
+//! for these messages carry an [`EventStorage`](../get_events/struct.EventStorage.html)
+//! pointing at the stored copy, which may be fetched either as parsed fields
+//! or as the raw MIME message, and resent to new recipients.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     ParamList,
+//! #     get_events::{GetEventsParam, GetEventsParamList},
+//! # };
+//! # let client = Client::new("", "");
+//! let request = GetEventsParamList::default()
+//!     .add(GetEventsParam::Event("stored"))
+//!     .add(GetEventsParam::Limit(1));
+//!
+//! let events = client.get_events(request).unwrap();
+//!
+//! if let Some(storage) = events.items.first().and_then(|event| event.storage.as_ref()) {
+//!     let message = client.get_stored_message(storage).unwrap();
+//!     let mime = client.get_stored_message_mime(storage).unwrap();
+//!
+//!     client.resend_stored_message(storage, "support@domain.com").unwrap();
+//! }
+//! ```
+//!
+//! [API Documentation](https://documentation.mailgun.com/en/latest/api-sending.html#retrieving-stored-messages)
+
+use std::collections::HashMap;
+
+//- Response
+
+/// Response returned by the stored message endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StoredMessage {
+    pub recipients: Option<String>,
+    pub sender: Option<String>,
+    pub from: Option<String>,
+    pub subject: Option<String>,
+    pub body_plain: Option<String>,
+    pub stripped_text: Option<String>,
+    pub stripped_signature: Option<String>,
+    pub body_html: Option<String>,
+    pub stripped_html: Option<String>,
+    #[serde(default)]
+    pub message_headers: Vec<(String, String)>,
+    #[serde(default)]
+    pub attachments: Vec<StoredMessageAttachment>,
+    #[serde(default)]
+    pub content_id_map: HashMap<String, StoredMessageAttachment>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A single attachment item found in [`StoredMessage`](struct.StoredMessage.html).
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StoredMessageAttachment {
+    pub url: String,
+    pub content_type: Option<String>,
+    pub name: Option<String>,
+    pub size: Option<i64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by the stored message endpoint when requesting the raw
+/// MIME message.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StoredMessageMime {
+    pub recipients: Option<String>,
+    pub sender: Option<String>,
+    pub from: Option<String>,
+    pub subject: Option<String>,
+    pub body_mime: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
@@ -17,7 +17,7 @@
 //!
 //! [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#unsubscribes)
 
-use crate::{Paging, Param, ParamError, ParamList};
+use crate::{InvalidItem, ItemList, Paging, Param, ParamError, ParamList};
 
 //- Request
 
@@ -37,19 +37,11 @@ impl Param for GetUnsubscribesParam {
 }
 
 /// List of parameters for fetching unsubscribes for a domain.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetUnsubscribesParamList {
     pub values: Vec<GetUnsubscribesParam>,
 }
 
-impl Default for GetUnsubscribesParamList {
-    fn default() -> Self {
-        Self {
-            values: vec![],
-        }
-    }
-}
-
 impl ParamList for GetUnsubscribesParamList {
     type ParamType = GetUnsubscribesParam;
 
@@ -67,6 +59,18 @@ impl ParamList for GetUnsubscribesParamList {
 pub struct GetUnsubscribesResponse {
     pub items: Vec<UnsubscribeItem>,
     pub paging: Paging,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetUnsubscribesResponse {
+    type Item = UnsubscribeItem;
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
 }
 
 /// A single item found in [`GetUnsubscribesResponse`](struct.GetUnsubscribesResponse.html).
@@ -75,4 +79,6 @@ pub struct UnsubscribeItem {
     pub address: String,
     pub tag: String,
     pub created_at: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
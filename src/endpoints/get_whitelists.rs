@@ -17,7 +17,7 @@
 //!
 //! [API Documentation](https://documentation.mailgun.com/en/latest/api-suppressions.html#view-all-whitelist-records)
 
-use crate::{Paging, Param, ParamError, ParamList};
+use crate::{InvalidItem, ItemList, Paging, Param, ParamError, ParamList};
 
 //- Request
 
@@ -37,19 +37,11 @@ impl Param for GetWhitelistsParam {
 }
 
 /// List of parameters for fetching whitelist records for a domain.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetWhitelistsParamList {
     pub values: Vec<GetWhitelistsParam>,
 }
 
-impl Default for GetWhitelistsParamList {
-    fn default() -> Self {
-        Self {
-            values: vec![],
-        }
-    }
-}
-
 impl ParamList for GetWhitelistsParamList {
     type ParamType = GetWhitelistsParam;
 
@@ -67,6 +59,18 @@ impl ParamList for GetWhitelistsParamList {
 pub struct GetWhitelistsResponse {
     pub items: Vec<WhitelistItem>,
     pub paging: Paging,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetWhitelistsResponse {
+    type Item = WhitelistItem;
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
 }
 
 /// A single item found in [`GetWhitelistsResponse`](struct.GetWhitelistsResponse.html).
@@ -77,4 +81,6 @@ pub struct WhitelistItem {
     pub reason: String,
     pub r#type: String,
     pub created_at: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
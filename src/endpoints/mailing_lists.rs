@@ -0,0 +1,387 @@
+//! Request and response module for managing mailing lists and their members.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     ParamList,
+//! #     mailing_lists::{
+//! #         AccessLevel,
+//! #         ListMember,
+//! #         ListMemberParam,
+//! #         ListMemberParamList,
+//! #         MailingListParam,
+//! #         MailingListParamList,
+//! #     },
+//! # };
+//! # let client = Client::new("", "");
+//! let request = MailingListParamList::default()
+//!     .add(MailingListParam::Address("newsletter@domain.com"))
+//!     .add(MailingListParam::Name("Newsletter"))
+//!     .add(MailingListParam::AccessLevel(AccessLevel::ReadOnly));
+//!
+//! client.create_mailing_list(request).unwrap();
+//!
+//! let vars = serde_json::json!({ "plan": "pro" });
+//! let request = ListMemberParamList::default()
+//!     .add(ListMemberParam::Address("Bob <bob@domain.com>"))
+//!     .add(ListMemberParam::Vars(&vars));
+//!
+//! client.add_list_member("newsletter@domain.com", request).unwrap();
+//!
+//! let members = vec![
+//!     ListMember::new("alice@domain.com"),
+//!     ListMember::new("carol@domain.com"),
+//! ];
+//!
+//! client.add_list_members("newsletter@domain.com", &members, true).unwrap();
+//! ```
+//!
+//! [API Documentation](https://documentation.mailgun.com/en/latest/api-mailinglists.html)
+
+use crate::address::{normalize_address, normalize_mailbox};
+use crate::{InvalidItem, ItemList, Paging, Param, ParamError, ParamList};
+
+use std::fmt;
+
+/// Maximum number of members that may be added in a single bulk request.
+pub const MAX_BULK_MEMBERS: usize = 1000;
+
+/// Who may post to a mailing list.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessLevel {
+    /// Only authenticated users can post.
+    ReadOnly,
+    /// Subscribed members can post.
+    Members,
+    /// Anyone can post.
+    Everyone,
+}
+
+impl fmt::Display for AccessLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ReadOnly => write!(f, "readonly"),
+            Self::Members => write!(f, "members"),
+            Self::Everyone => write!(f, "everyone"),
+        }
+    }
+}
+
+/// Where replies to a mailing list message are sent.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplyPreference {
+    /// Replies go to the mailing list.
+    List,
+    /// Replies go to the sender of the message.
+    Sender,
+}
+
+impl fmt::Display for ReplyPreference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::List => write!(f, "list"),
+            Self::Sender => write!(f, "sender"),
+        }
+    }
+}
+
+//- Request
+
+/// A parameter for fetching mailing lists.
+#[derive(Debug)]
+pub enum GetMailingListsParam {
+    /// Maximum number of records to return (default: 100).
+    Limit(usize),
+}
+
+impl Param for GetMailingListsParam {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Limit(v) => ("limit".to_string(), v.to_string()),
+        })
+    }
+}
+
+/// List of parameters for fetching mailing lists.
+#[derive(Debug, Default)]
+pub struct GetMailingListsParamList {
+    pub values: Vec<GetMailingListsParam>,
+}
+
+impl ParamList for GetMailingListsParamList {
+    type ParamType = GetMailingListsParam;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+/// A parameter for creating or updating a mailing list.
+#[derive(Debug)]
+pub enum MailingListParam<'a> {
+    /// Address of the mailing list. Required when creating a list.
+    Address(&'a str),
+    /// Display name of the mailing list.
+    Name(&'a str),
+    /// Description of the mailing list.
+    Description(&'a str),
+    /// Who may post to the mailing list (default: readonly).
+    AccessLevel(AccessLevel),
+    /// Where replies to the mailing list are sent (default: list).
+    ReplyPreference(ReplyPreference),
+}
+
+impl<'a> Param for MailingListParam<'a> {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Address(v) => ("address".to_string(), normalize_address("address", v)?),
+            Self::Name(v) => ("name".to_string(), v.to_string()),
+            Self::Description(v) => ("description".to_string(), v.to_string()),
+            Self::AccessLevel(v) => ("access_level".to_string(), v.to_string()),
+            Self::ReplyPreference(v) => ("reply_preference".to_string(), v.to_string()),
+        })
+    }
+}
+
+/// List of parameters for creating or updating a mailing list.
+#[derive(Debug, Default)]
+pub struct MailingListParamList<'a> {
+    pub values: Vec<MailingListParam<'a>>,
+}
+
+impl<'a> ParamList for MailingListParamList<'a> {
+    type ParamType = MailingListParam<'a>;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+/// A parameter for fetching the members of a mailing list.
+#[derive(Debug)]
+pub enum GetListMembersParam {
+    /// Only return subscribed (`true`) or unsubscribed (`false`) members.
+    Subscribed(bool),
+    /// Maximum number of records to return (default: 100).
+    Limit(usize),
+}
+
+impl Param for GetListMembersParam {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Subscribed(v) => ("subscribed".to_string(), if *v { "yes".to_string() } else { "no".to_string() }),
+            Self::Limit(v) => ("limit".to_string(), v.to_string()),
+        })
+    }
+}
+
+/// List of parameters for fetching the members of a mailing list.
+#[derive(Debug, Default)]
+pub struct GetListMembersParamList {
+    pub values: Vec<GetListMembersParam>,
+}
+
+impl ParamList for GetListMembersParamList {
+    type ParamType = GetListMembersParam;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+/// A parameter for adding or updating a single mailing list member.
+#[derive(Debug)]
+pub enum ListMemberParam<'a> {
+    /// Address of the member. Example: "Bob <bob@host.com>". Required when
+    /// adding a member.
+    Address(&'a str),
+    /// Display name of the member.
+    Name(&'a str),
+    /// Custom JSON data attached to the member.
+    Vars(&'a serde_json::Value),
+    /// Whether the member is subscribed (default: `true`).
+    Subscribed(bool),
+    /// When adding a member, update it if it already exists instead of
+    /// failing (default: `false`).
+    Upsert(bool),
+}
+
+impl<'a> Param for ListMemberParam<'a> {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Address(v) => ("address".to_string(), normalize_mailbox("address", v)?),
+            Self::Name(v) => ("name".to_string(), v.to_string()),
+            Self::Vars(v) => {
+                let v = serde_json::to_string(v)
+                    .map_err(|error| ParamError::InvalidJson("vars".to_string(), error))?;
+
+                ("vars".to_string(), v)
+            },
+            Self::Subscribed(v) => ("subscribed".to_string(), if *v { "yes".to_string() } else { "no".to_string() }),
+            Self::Upsert(v) => ("upsert".to_string(), if *v { "yes".to_string() } else { "no".to_string() }),
+        })
+    }
+}
+
+/// List of parameters for adding or updating a single mailing list member.
+#[derive(Debug, Default)]
+pub struct ListMemberParamList<'a> {
+    pub values: Vec<ListMemberParam<'a>>,
+}
+
+impl<'a> ParamList for ListMemberParamList<'a> {
+    type ParamType = ListMemberParam<'a>;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+//- Response
+
+/// Response returned by get mailing lists endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetMailingListsResponse {
+    pub items: Vec<MailingList>,
+    pub paging: Paging,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetMailingListsResponse {
+    type Item = MailingList;
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
+}
+
+/// A single mailing list.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MailingList {
+    pub address: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub access_level: AccessLevel,
+    pub reply_preference: Option<ReplyPreference>,
+    pub members_count: i64,
+    pub created_at: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned when fetching, creating or updating a mailing list.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MailingListResponse {
+    pub list: MailingList,
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by delete mailing list endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeleteMailingListResponse {
+    pub address: String,
+    pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by get mailing list members endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetListMembersResponse {
+    pub items: Vec<ListMember>,
+    pub paging: Paging,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetListMembersResponse {
+    type Item = ListMember;
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
+}
+
+/// A single mailing list member.
+///
+/// This is also used to describe each member when adding members in bulk.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ListMember {
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub vars: serde_json::Value,
+    pub subscribed: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ListMember {
+    /// Create a subscribed member with no name or vars.
+    pub fn new(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            name: None,
+            vars: serde_json::Value::Null,
+            subscribed: true,
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+/// Response returned when fetching, adding or updating a mailing list member.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ListMemberResponse {
+    pub member: ListMember,
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by delete mailing list member endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeleteListMemberResponse {
+    pub member: DeletedListMember,
+    pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A single deleted member found in [`DeleteListMemberResponse`](struct.DeleteListMemberResponse.html).
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeletedListMember {
+    pub address: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned when adding members in bulk, either as JSON or CSV.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AddListMembersResponse {
+    pub list: Option<MailingList>,
+    pub message: String,
+    #[serde(rename = "task-id")]
+    pub task_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
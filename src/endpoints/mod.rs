@@ -1,12 +1,24 @@
 //! API endpoints and their request/response structures.
+//!
+//! Every response type has an `extra` field holding any fields returned by
+//! the API that are not known to this crate.
 
+pub mod account_stats;
 pub mod get_bounces;
 pub mod get_complaints;
 pub mod get_events;
 pub mod get_stats;
+pub mod get_stored_message;
 pub mod get_unsubscribes;
 pub mod get_whitelists;
+pub mod mailing_lists;
+pub mod routes;
 pub mod send_message;
+pub mod tags;
+pub mod validations;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 
 /// Structure of pagination data returned by some API endpoints.
 #[derive(Debug, Deserialize, Serialize)]
@@ -15,4 +27,76 @@ pub struct Paging {
     pub next: String,
     pub previous: String,
     pub last: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A response holding a list of items.
+///
+/// When a client is [`lenient`](../struct.Client.html#method.lenient), items
+/// of these responses that cannot be decoded are left out of the list and
+/// kept in its `invalid_items` field, instead of failing the whole response.
+pub trait ItemList: DeserializeOwned {
+    type Item: DeserializeOwned;
+
+    /// Name of the field holding the items.
+    const FIELD: &'static str = "items";
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem>;
+}
+
+/// An item of an [`ItemList`](trait.ItemList.html) that could not be decoded.
+#[derive(Clone, Debug)]
+pub struct InvalidItem {
+    /// Position of the item in the response.
+    pub index: usize,
+    pub raw: serde_json::Value,
+    pub error: String,
+}
+
+/// Decode a list response, leaving out the items that cannot be decoded.
+pub(crate) fn decode_lenient<T: ItemList>(raw: &str) -> Result<T, serde_json::Error> {
+    let mut value: serde_json::Value = serde_json::from_str(raw)?;
+    let mut invalid_items = vec![];
+
+    if let Some(items) = value.get_mut(T::FIELD).and_then(serde_json::Value::as_array_mut) {
+        for (index, item) in std::mem::take(items).into_iter().enumerate() {
+            match T::Item::deserialize(&item) {
+                Ok(_) => items.push(item),
+                Err(error) => invalid_items.push(InvalidItem { index, raw: item, error: error.to_string() }),
+            }
+        }
+    }
+
+    let mut list: T = serde_json::from_value(value)?;
+    *list.invalid_items_mut() = invalid_items;
+
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use get_bounces::GetBouncesResponse;
+
+    #[test]
+    fn lenient_items() {
+        let raw = r#"{
+            "items": [
+                { "address": "alice@example.com", "code": "550", "error": "No such user", "created_at": "Fri, 21 Oct 2011 11:02:55 GMT", "mta": "mx1" },
+                { "address": "bob@example.com" }
+            ],
+            "paging": { "next": "https://api.mailgun.net/v3/next", "previous": "https://api.mailgun.net/v3/previous" }
+        }"#;
+
+        assert!(serde_json::from_str::<GetBouncesResponse>(raw).is_err());
+
+        let response: GetBouncesResponse = decode_lenient(raw).unwrap();
+
+        assert_eq!(response.items.len(), 1);
+        assert_eq!(response.items[0].extra.get("mta"), Some(&serde_json::json!("mx1")));
+        assert_eq!(response.invalid_items.len(), 1);
+        assert_eq!(response.invalid_items[0].index, 1);
+        assert_eq!(response.invalid_items[0].raw["address"], "bob@example.com");
+    }
 }
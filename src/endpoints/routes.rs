@@ -0,0 +1,327 @@
+//! Request and response module for managing routes for inbound messages.
+//!
+//! Routes are made of a filter expression, deciding which messages the route
+//! applies to, and a list of actions to take on those messages. Both are
+//! built with [`Filter`](enum.Filter.html) and [`Action`](enum.Action.html),
+//! which render the expression syntax expected by the API.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     ParamList,
+//! #     routes::{Action, Filter, RouteParam, RouteParamList},
+//! # };
+//! # let client = Client::new("", "");
+//! let filter = Filter::match_recipient("support@domain.com")
+//!     .and(Filter::match_header("subject", ".*urgent"));
+//!
+//! let request = RouteParamList::default()
+//!     .add(RouteParam::Priority(0))
+//!     .add(RouteParam::Description("Urgent support"))
+//!     .add(RouteParam::Expression(filter))
+//!     .add(RouteParam::Action(Action::forward("https://domain.com/inbound")))
+//!     .add(RouteParam::Action(Action::stop()));
+//!
+//! client.create_route(request).unwrap();
+//! ```
+//!
+//! [API Documentation](https://documentation.mailgun.com/en/latest/api-routes.html)
+
+use crate::{InvalidItem, ItemList, Param, ParamError, ParamList};
+
+use std::fmt;
+
+/// A filter expression deciding which messages a route applies to.
+///
+/// ```
+/// # use mailgun_sdk::routes::Filter;
+/// let filter = Filter::match_recipient(".*@domain.com")
+///     .and(Filter::match_header("subject", ".*support"));
+///
+/// assert_eq!(
+///     filter.to_string(),
+///     r#"match_recipient(".*@domain.com") and match_header("subject", ".*support")"#,
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Filter {
+    /// Matches the SMTP recipient of the message against a regular
+    /// expression.
+    MatchRecipient(String),
+    /// Matches a MIME header of the message against a regular expression.
+    MatchHeader { header: String, pattern: String },
+    /// Matches every message.
+    CatchAll,
+    /// Matches messages that match all of the filters.
+    And(Vec<Filter>),
+}
+
+impl Filter {
+    /// Match the SMTP recipient against `pattern`.
+    pub fn match_recipient(pattern: &str) -> Self {
+        Self::MatchRecipient(pattern.to_string())
+    }
+
+    /// Match the value of `header` against `pattern`.
+    pub fn match_header(header: &str, pattern: &str) -> Self {
+        Self::MatchHeader {
+            header: header.to_string(),
+            pattern: pattern.to_string(),
+        }
+    }
+
+    /// Match every message.
+    pub fn catch_all() -> Self {
+        Self::CatchAll
+    }
+
+    /// Combine this filter with `other`, so that both must match.
+    pub fn and(self, other: Filter) -> Self {
+        match (self, other) {
+            (Self::And(mut left), Self::And(right)) => {
+                left.extend(right);
+                Self::And(left)
+            },
+            (Self::And(mut left), right) => {
+                left.push(right);
+                Self::And(left)
+            },
+            (left, Self::And(mut right)) => {
+                right.insert(0, left);
+                Self::And(right)
+            },
+            (left, right) => Self::And(vec![left, right]),
+        }
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MatchRecipient(pattern) => write!(f, "match_recipient({})", quote(pattern)),
+            Self::MatchHeader { header, pattern } => write!(f, "match_header({}, {})", quote(header), quote(pattern)),
+            Self::CatchAll => write!(f, "catch_all()"),
+            Self::And(filters) => {
+                for (index, filter) in filters.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " and ")?;
+                    }
+
+                    write!(f, "{}", filter)?;
+                }
+
+                Ok(())
+            },
+        }
+    }
+}
+
+/// An action taken on messages matching a route.
+///
+/// ```
+/// # use mailgun_sdk::routes::Action;
+/// assert_eq!(Action::forward("ops@domain.com").to_string(), r#"forward("ops@domain.com")"#);
+/// assert_eq!(Action::store(Some("https://domain.com/notify")).to_string(), r#"store(notify="https://domain.com/notify")"#);
+/// assert_eq!(Action::stop().to_string(), "stop()");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Forward the message to an email address or URL.
+    Forward(String),
+    /// Store the message temporarily, optionally notifying a URL.
+    Store { notify: Option<String> },
+    /// Stop evaluating lower priority routes.
+    Stop,
+}
+
+impl Action {
+    /// Forward the message to `destination`, either an email address or a
+    /// URL.
+    pub fn forward(destination: &str) -> Self {
+        Self::Forward(destination.to_string())
+    }
+
+    /// Store the message, and `POST` a notification to `notify` if given.
+    pub fn store(notify: Option<&str>) -> Self {
+        Self::Store {
+            notify: notify.map(|notify| notify.to_string()),
+        }
+    }
+
+    /// Stop evaluating lower priority routes.
+    pub fn stop() -> Self {
+        Self::Stop
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Forward(destination) => write!(f, "forward({})", quote(destination)),
+            Self::Store { notify: Some(notify) } => write!(f, "store(notify={})", quote(notify)),
+            Self::Store { notify: None } => write!(f, "store()"),
+            Self::Stop => write!(f, "stop()"),
+        }
+    }
+}
+
+/// Render a value as a double-quoted string argument. Backslashes are kept as
+/// is, since they are usually part of a regular expression.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\\\""))
+}
+
+//- Request
+
+/// A parameter for fetching routes.
+#[derive(Debug)]
+pub enum GetRoutesParam {
+    /// Number of records to skip (default: 0).
+    Skip(usize),
+    /// Maximum number of records to return (default: 100).
+    Limit(usize),
+}
+
+impl Param for GetRoutesParam {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Skip(v) => ("skip".to_string(), v.to_string()),
+            Self::Limit(v) => ("limit".to_string(), v.to_string()),
+        })
+    }
+}
+
+/// List of parameters for fetching routes.
+#[derive(Debug, Default)]
+pub struct GetRoutesParamList {
+    pub values: Vec<GetRoutesParam>,
+}
+
+impl ParamList for GetRoutesParamList {
+    type ParamType = GetRoutesParam;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+/// A parameter for creating or updating a route.
+#[derive(Debug)]
+pub enum RouteParam<'a> {
+    /// Routes with a smaller number are evaluated first (default: 0).
+    Priority(i64),
+    /// Arbitrary description of the route.
+    Description(&'a str),
+    /// Filter deciding which messages the route applies to.
+    Expression(Filter),
+    /// Action to take on matching messages. You can post multiple action
+    /// values.
+    Action(Action),
+}
+
+impl<'a> Param for RouteParam<'a> {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Priority(v) => ("priority".to_string(), v.to_string()),
+            Self::Description(v) => ("description".to_string(), v.to_string()),
+            Self::Expression(v) => ("expression".to_string(), v.to_string()),
+            Self::Action(v) => ("action".to_string(), v.to_string()),
+        })
+    }
+}
+
+/// List of parameters for creating or updating a route.
+#[derive(Debug, Default)]
+pub struct RouteParamList<'a> {
+    pub values: Vec<RouteParam<'a>>,
+}
+
+impl<'a> ParamList for RouteParamList<'a> {
+    type ParamType = RouteParam<'a>;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+//- Response
+
+/// Response returned by get routes endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetRoutesResponse {
+    pub total_count: i64,
+    pub items: Vec<Route>,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetRoutesResponse {
+    type Item = Route;
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
+}
+
+/// A single route.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Route {
+    pub id: String,
+    pub priority: i64,
+    pub description: String,
+    pub expression: String,
+    pub actions: Vec<String>,
+    pub created_at: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned when fetching or creating a route.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RouteResponse {
+    pub route: Route,
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by update route endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateRouteResponse {
+    #[serde(flatten)]
+    pub route: Route,
+    pub message: String,
+}
+
+/// Response returned by delete route endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeleteRouteResponse {
+    pub id: String,
+    pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_expression() {
+        let filter = Filter::catch_all()
+            .and(Filter::match_header("x-tag", r#"say "hi""#).and(Filter::match_recipient(r"^chris\+(.*)@domain\.com$")));
+
+        assert_eq!(
+            filter.to_string(),
+            r#"catch_all() and match_header("x-tag", "say \"hi\"") and match_recipient("^chris\+(.*)@domain\.com$")"#,
+        );
+    }
+}
@@ -4,6 +4,11 @@
 //! you notice any that do not work, please feel free to create a ticket, or
 //! create a pull a request.
 //!
+//! The `From`, `To`, `Cc` and `Bcc` parameters are parsed as
+//! [`Mailbox`](../struct.Mailbox.html) values before the message is sent, and
+//! an invalid address results in a
+//! [`ParamError::InvalidAddress`](../enum.ParamError.html#variant.InvalidAddress).
+//!
 //! ### Example
 //!
 //! ```no_run
@@ -21,10 +26,60 @@
 //! client.send_message(params).unwrap();
 //! ```
 //!
+//! ### Validation
+//!
+//! Before a message is sent, its parameters are checked against the API's
+//! documented limits, such as the maximum number of tags and how far in the
+//! future delivery can be scheduled. Every violation is reported at once in a
+//! [`ParamError::Validation`](../enum.ParamError.html#variant.Validation). Call
+//! `without_validation` on the parameter list to skip this step, including the
+//! parsing of addresses, which are then sent exactly as given.
+//!
+//! ### MIME Messages
+//!
+//! Messages that are already rendered in MIME format can be sent with
+//! `send_mime`. Only the `o:`, `h:` and `v:` options may be given alongside
+//! the message.
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     ParamList,
+//! #     send_message::{SendMessageParam, SendMessageParamList},
+//! # };
+//! # let client = Client::new("", "");
+//! let mime = std::fs::read("message.eml").unwrap();
+//! let params = SendMessageParamList::default()
+//!     .add(SendMessageParam::OTag("newsletter"));
+//!
+//! client.send_mime("you@domain.com", &mime, params).unwrap();
+//! ```
+//!
 //! [API Documentation](https://documentation.mailgun.com/en/latest/api-sending.html#sending)
 
+use crate::address::{normalize_mailbox, normalize_mailbox_list};
 use crate::{Param, ParamError, ParamList};
 
+use chrono::{DateTime, Duration, Utc};
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Maximum number of `o:tag` values on a single message.
+pub const MAX_TAGS: usize = 3;
+
+/// Maximum length of a single tag.
+pub const MAX_TAG_LENGTH: usize = 128;
+
+/// Maximum number of days a message may be scheduled in the future.
+pub const MAX_DELIVERY_DAYS: i64 = 3;
+
+/// Maximum length of a custom header value, as per RFC 5322.
+pub const MAX_HEADER_LENGTH: usize = 998;
+
+/// Maximum combined size, in bytes, of the custom variables on a message.
+pub const MAX_VARIABLES_SIZE: usize = 4096;
+
 //- Request
 
 /// A parameter for sending message from a domain.
@@ -62,16 +117,16 @@ pub enum SendMessageParam<'a, T: ?Sized> where T: serde::Serialize {
     ODkim(bool),
     /// Desired time of delivery. Note: Messages can be scheduled for a maximum of 3 days in the future.
     ODeliveryTime(&'a str),
-    /// Toggles Send Time Optimization (STO) on a per-message basis. String should be set to the number of hours in `[0-9]+h` format, with the minimum being `24h` and the maximum being `72h`. This value defines the time window in which Mailgun will run the optimization algorithm based on prior engagement data of a given recipient. _Please note that STO is only available on certain plans._
-    ODeliveryTimeOptimizePeriod(&'a str),
-    /// Toggles Timezone Optimization (TZO) on a per message basis. String should be set to preferred delivery time in `HH:mm` or `hh:mmaa` format, where `HH:mm` is used for 24 hour format without AM/PM and `hh:mmaa` is used for 12 hour format with AM/PM. See Sending a message with TZO for details. _Please note that TZO is only available on certain plans._
-    OTimeZoneLocalize(&'a str),
+    /// Toggles Send Time Optimization (STO) on a per-message basis. The period is the number of hours, with the minimum being `24h` and the maximum being `72h`. This value defines the time window in which Mailgun will run the optimization algorithm based on prior engagement data of a given recipient. _Please note that STO is only available on certain plans._
+    ODeliveryTimeOptimizePeriod(StoPeriod),
+    /// Toggles Timezone Optimization (TZO) on a per message basis. The time is the preferred delivery time in the recipient's time zone. See Sending a message with TZO for details. _Please note that TZO is only available on certain plans._
+    OTimeZoneLocalize(LocalTime),
     /// Enables sending in test mode.
     OTestMode(bool),
     /// Toggles tracking on a per-message basis.
     OTracking(bool),
-    /// Toggles clicks tracking on a per-message basis. Has higher priority than domain-level setting. Pass `yes`, `no`, `true`, `false` or `htmlonly`.
-    OTrackingClicks(&'a str),
+    /// Toggles clicks tracking on a per-message basis. Has higher priority than domain-level setting.
+    OTrackingClicks(ClickTracking),
     /// Toggles opens tracking on a per-message basis. Has higher priority than domain-level setting.
     OTrackingOpens(bool),
     /// If `true`, the message will only be sent over a TLS connection. The message will fail if a TLS connection cannot be established. If `false`, MailGun will try to upgrade the connection, but still send over a plaintext STMP connection on failure.
@@ -86,13 +141,41 @@ pub enum SendMessageParam<'a, T: ?Sized> where T: serde::Serialize {
     RecipientVariables(&'a T),
 }
 
-impl<'a, T: ?Sized> Param for SendMessageParam<'a, T> where T: serde::Serialize {
-    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+impl<'a, T: ?Sized> SendMessageParam<'a, T> where T: serde::Serialize {
+    /// Whether this is an `o:`, `h:` or `v:` option, which are the only
+    /// parameters accepted when sending a MIME message.
+    pub fn is_option(&self) -> bool {
+        matches!(self,
+            Self::OTag(_)
+            | Self::ODkim(_)
+            | Self::ODeliveryTime(_)
+            | Self::ODeliveryTimeOptimizePeriod(_)
+            | Self::OTimeZoneLocalize(_)
+            | Self::OTestMode(_)
+            | Self::OTracking(_)
+            | Self::OTrackingClicks(_)
+            | Self::OTrackingOpens(_)
+            | Self::ORequireTls(_)
+            | Self::OSkipVerification(_)
+            | Self::CustomHeader { .. }
+            | Self::CustomVariable { .. }
+        )
+    }
+}
+
+impl<'a, T: ?Sized> SendMessageParam<'a, T> where T: serde::Serialize {
+    /// Render the parameter, parsing and normalizing addresses unless
+    /// `parse_addresses` is false.
+    pub(crate) fn render(&self, parse_addresses: bool) -> Result<(String, String), ParamError> {
         Ok(match self {
-            Self::From(v) => ("from".to_string(), v.to_string()),
-            Self::To(v) => ("to".to_string(), v.to_string()),
-            Self::Cc(v) => ("cc".to_string(), v.to_string()),
-            Self::Bcc(v) => ("bcc".to_string(), v.to_string()),
+            Self::From(v) if !parse_addresses => ("from".to_string(), v.to_string()),
+            Self::To(v) if !parse_addresses => ("to".to_string(), v.to_string()),
+            Self::Cc(v) if !parse_addresses => ("cc".to_string(), v.to_string()),
+            Self::Bcc(v) if !parse_addresses => ("bcc".to_string(), v.to_string()),
+            Self::From(v) => ("from".to_string(), normalize_mailbox("from", v)?),
+            Self::To(v) => ("to".to_string(), normalize_mailbox_list("to", v)?),
+            Self::Cc(v) => ("cc".to_string(), normalize_mailbox_list("cc", v)?),
+            Self::Bcc(v) => ("bcc".to_string(), normalize_mailbox_list("bcc", v)?),
             Self::Subject(v) => ("subject".to_string(), v.to_string()),
             Self::Text(v) => ("text".to_string(), v.to_string()),
             Self::Html(v) => ("html".to_string(), v.to_string()),
@@ -125,16 +208,318 @@ impl<'a, T: ?Sized> Param for SendMessageParam<'a, T> where T: serde::Serialize
     }
 }
 
+impl<'a, T: ?Sized> Param for SendMessageParam<'a, T> where T: serde::Serialize {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        self.render(true)
+    }
+}
+
+/// Click tracking setting for [`SendMessageParam::OTrackingClicks`](enum.SendMessageParam.html#variant.OTrackingClicks).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClickTracking {
+    /// Track clicks in both the HTML and text parts.
+    Yes,
+    /// Do not track clicks.
+    No,
+    /// Only track clicks in the HTML part.
+    HtmlOnly,
+}
+
+impl FromStr for ClickTracking {
+    type Err = ParamError;
+
+    /// Parse one of `yes`, `no`, `true`, `false` or `htmlonly`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "yes" | "true" => Ok(Self::Yes),
+            "no" | "false" => Ok(Self::No),
+            "htmlonly" => Ok(Self::HtmlOnly),
+            _ => Err(ParamError::InvalidValue(
+                "o:tracking-clicks".to_string(),
+                format!("expected yes, no, true, false or htmlonly, got '{}'", s),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ClickTracking {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Yes => write!(f, "yes"),
+            Self::No => write!(f, "no"),
+            Self::HtmlOnly => write!(f, "htmlonly"),
+        }
+    }
+}
+
+/// Send Time Optimization period for [`SendMessageParam::ODeliveryTimeOptimizePeriod`](enum.SendMessageParam.html#variant.ODeliveryTimeOptimizePeriod).
+///
+/// ```
+/// # use mailgun_sdk::send_message::StoPeriod;
+/// let period = StoPeriod::hours(48).unwrap();
+///
+/// assert_eq!(period.to_string(), "48h");
+/// assert!(StoPeriod::hours(12).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StoPeriod(u8);
+
+impl StoPeriod {
+    /// Shortest period accepted by the API.
+    pub const MIN_HOURS: u8 = 24;
+
+    /// Longest period accepted by the API.
+    pub const MAX_HOURS: u8 = 72;
+
+    /// Create a period of `hours`, which must be between 24 and 72.
+    pub fn hours(hours: u8) -> Result<Self, ParamError> {
+        if !(Self::MIN_HOURS..=Self::MAX_HOURS).contains(&hours) {
+            return Err(ParamError::InvalidValue(
+                "o:delivery-time-optimize-period".to_string(),
+                format!("expected between {}h and {}h, got {}h", Self::MIN_HOURS, Self::MAX_HOURS, hours),
+            ));
+        }
+
+        Ok(Self(hours))
+    }
+
+    /// Number of hours in the period.
+    pub fn as_hours(&self) -> u8 {
+        self.0
+    }
+}
+
+impl FromStr for StoPeriod {
+    type Err = ParamError;
+
+    /// Parse a period in `[0-9]+h` format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.strip_suffix('h')
+            .filter(|hours| !hours.is_empty() && hours.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|hours| hours.parse().ok())
+            .ok_or_else(|| ParamError::InvalidValue(
+                "o:delivery-time-optimize-period".to_string(),
+                format!("expected [0-9]+h, got '{}'", s),
+            ))
+            .and_then(Self::hours)
+    }
+}
+
+impl fmt::Display for StoPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}h", self.0)
+    }
+}
+
+/// Preferred delivery time for [`SendMessageParam::OTimeZoneLocalize`](enum.SendMessageParam.html#variant.OTimeZoneLocalize).
+///
+/// ```
+/// # use mailgun_sdk::send_message::LocalTime;
+/// let time: LocalTime = "06:30pm".parse().unwrap();
+///
+/// assert_eq!(time, LocalTime::new(18, 30).unwrap());
+/// assert_eq!(time.to_string(), "18:30");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocalTime {
+    hour: u8,
+    minute: u8,
+}
+
+impl LocalTime {
+    /// Create a time from a 24 hour clock `hour` and `minute`.
+    pub fn new(hour: u8, minute: u8) -> Result<Self, ParamError> {
+        if hour > 23 || minute > 59 {
+            return Err(ParamError::InvalidValue(
+                "o:time-zone-localize".to_string(),
+                format!("{}:{} is not a valid time", hour, minute),
+            ));
+        }
+
+        Ok(Self { hour, minute })
+    }
+
+    /// Hour of the day, between 0 and 23.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Minute of the hour, between 0 and 59.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+}
+
+impl FromStr for LocalTime {
+    type Err = ParamError;
+
+    /// Parse a time in `HH:mm` (24 hour) or `hh:mmaa` (12 hour) format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParamError::InvalidValue(
+            "o:time-zone-localize".to_string(),
+            format!("expected HH:mm or hh:mmaa, got '{}'", s),
+        );
+
+        let lower = s.trim().to_ascii_lowercase();
+        let (time, meridiem) = match lower.strip_suffix("am") {
+            Some(time) => (time, Some(false)),
+            None => match lower.strip_suffix("pm") {
+                Some(time) => (time, Some(true)),
+                None => (lower.as_str(), None),
+            },
+        };
+
+        let (hour, minute) = time.trim_end().split_once(':').ok_or_else(invalid)?;
+
+        let is_number = |value: &str| value.bytes().all(|b| b.is_ascii_digit());
+
+        if hour.is_empty() || hour.len() > 2 || minute.len() != 2 || !is_number(hour) || !is_number(minute) {
+            return Err(invalid());
+        }
+
+        let hour: u8 = hour.parse().map_err(|_| invalid())?;
+        let minute: u8 = minute.parse().map_err(|_| invalid())?;
+
+        let hour = match meridiem {
+            Some(_) if hour == 0 || hour > 12 => return Err(invalid()),
+            Some(false) => hour % 12,
+            Some(true) => hour % 12 + 12,
+            None => hour,
+        };
+
+        Self::new(hour, minute)
+    }
+}
+
+impl fmt::Display for LocalTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+fn invalid(key: &str, reason: String) -> ParamError {
+    ParamError::InvalidValue(key.to_string(), reason)
+}
+
 /// List of parameters for sending message from a domain.
 #[derive(Debug)]
 pub struct SendMessageParamList<'a, T: ?Sized> where T: serde::Serialize {
     pub values: Vec<SendMessageParam<'a, T>>,
+    /// Send the message without running `validate` first. Addresses are then
+    /// sent as given instead of being parsed and normalized.
+    pub skip_validation: bool,
+}
+
+impl<'a, T: ?Sized> SendMessageParamList<'a, T> where T: serde::Serialize {
+    /// Skip the validation step when sending the message, including the
+    /// parsing of addresses.
+    pub fn without_validation(mut self) -> Self {
+        self.skip_validation = true;
+
+        self
+    }
+
+    /// Check the parameters against the API's documented limits.
+    ///
+    /// All violations are returned together in a `ParamError::Validation`.
+    pub fn validate(&self) -> Result<(), ParamError> {
+        self.validated_pairs().map(|_| ())
+    }
+
+    /// Render the parameters to send, validating them first unless
+    /// validation is skipped.
+    pub(crate) fn to_pairs(&self) -> Result<Vec<(String, String)>, ParamError> {
+        if self.skip_validation {
+            self.values.iter().map(|param| param.render(false)).collect()
+        } else {
+            self.validated_pairs()
+        }
+    }
+
+    fn validated_pairs(&self) -> Result<Vec<(String, String)>, ParamError> {
+        let mut pairs = Vec::with_capacity(self.values.len());
+        let mut errors = vec![];
+        let mut tags = 0;
+        let mut variables_size = 0;
+        let mut has_template = false;
+        let mut has_template_version = false;
+
+        for param in self.values.iter() {
+            // Addresses and other formatted values are checked when the
+            // parameter is rendered.
+            match param.try_as_tuple() {
+                Ok(pair) => pairs.push(pair),
+                Err(error) => errors.push(error),
+            }
+
+            match param {
+                SendMessageParam::OTag(tag) => {
+                    tags += 1;
+
+                    if tag.is_empty() || tag.len() > MAX_TAG_LENGTH {
+                        errors.push(invalid("o:tag", format!("'{}' must be between 1 and {} characters", tag, MAX_TAG_LENGTH)));
+                    } else if !tag.chars().all(|c| c.is_ascii_graphic() || c == ' ') {
+                        errors.push(invalid("o:tag", format!("'{}' must only contain printable ASCII characters", tag)));
+                    }
+                },
+                SendMessageParam::ODeliveryTime(time) => {
+                    match DateTime::parse_from_rfc2822(time) {
+                        Ok(time) if time.with_timezone(&Utc) > Utc::now() + Duration::days(MAX_DELIVERY_DAYS) => {
+                            errors.push(invalid("o:delivery-time", format!("must be at most {} days in the future", MAX_DELIVERY_DAYS)));
+                        },
+                        Ok(_) => {},
+                        Err(_) => {
+                            errors.push(invalid("o:delivery-time", format!("'{}' is not an RFC 2822 date", time)));
+                        },
+                    }
+                },
+                SendMessageParam::CustomHeader { key, value } => {
+                    let name = format!("h:{}", key);
+
+                    if key.is_empty() || !key.chars().all(|c| c.is_ascii_graphic() && c != ':') {
+                        errors.push(invalid(&name, "header name must be printable ASCII without ':'".to_string()));
+                    }
+
+                    if value.contains(['\r', '\n']) {
+                        errors.push(invalid(&name, "header value must not contain line breaks".to_string()));
+                    } else if value.len() > MAX_HEADER_LENGTH {
+                        errors.push(invalid(&name, format!("header value must be at most {} bytes", MAX_HEADER_LENGTH)));
+                    }
+                },
+                SendMessageParam::CustomVariable { key, value } => {
+                    variables_size += key.len() + value.len();
+                },
+                SendMessageParam::Template(_) => has_template = true,
+                SendMessageParam::TVersion(_) => has_template_version = true,
+                _ => {},
+            }
+        }
+
+        if tags > MAX_TAGS {
+            errors.push(invalid("o:tag", format!("at most {} tags are allowed, got {}", MAX_TAGS, tags)));
+        }
+
+        if variables_size > MAX_VARIABLES_SIZE {
+            errors.push(invalid("v:", format!("custom variables must be at most {} bytes in total, got {}", MAX_VARIABLES_SIZE, variables_size)));
+        }
+
+        if has_template_version && !has_template {
+            errors.push(invalid("t:version", "requires a 'template' parameter".to_string()));
+        }
+
+        if errors.is_empty() {
+            Ok(pairs)
+        } else {
+            Err(ParamError::Validation(errors))
+        }
+    }
 }
 
 impl<'a, T: ?Sized> Default for SendMessageParamList<'a, T> where T: serde::Serialize {
     fn default() -> Self {
         Self {
             values: vec![],
+            skip_validation: false,
         }
     }
 }
@@ -156,4 +541,96 @@ impl<'a, T: ?Sized> ParamList for SendMessageParamList<'a, T> where T: serde::Se
 pub struct SendMessageResponse {
     pub id: String,
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn click_tracking() {
+        assert_eq!("true".parse::<ClickTracking>().unwrap(), ClickTracking::Yes);
+        assert_eq!("NO".parse::<ClickTracking>().unwrap(), ClickTracking::No);
+        assert_eq!("htmlonly".parse::<ClickTracking>().unwrap().to_string(), "htmlonly");
+        assert!("sometimes".parse::<ClickTracking>().is_err());
+    }
+
+    #[test]
+    fn sto_period() {
+        assert_eq!("72h".parse::<StoPeriod>().unwrap().as_hours(), 72);
+        assert!("73h".parse::<StoPeriod>().is_err());
+        assert!("48".parse::<StoPeriod>().is_err());
+        assert!("h".parse::<StoPeriod>().is_err());
+    }
+
+    #[test]
+    fn local_time() {
+        assert_eq!("09:05".parse::<LocalTime>().unwrap().to_string(), "09:05");
+        assert_eq!("12:15am".parse::<LocalTime>().unwrap().to_string(), "00:15");
+        assert_eq!("12:15PM".parse::<LocalTime>().unwrap().to_string(), "12:15");
+        assert!("13:00pm".parse::<LocalTime>().is_err());
+        assert!("24:00".parse::<LocalTime>().is_err());
+        assert!("9:5".parse::<LocalTime>().is_err());
+        assert!("+9:30".parse::<LocalTime>().is_err());
+        assert!("09:+5".parse::<LocalTime>().is_err());
+    }
+
+    #[test]
+    fn validate() {
+        let delivery_time = (Utc::now() + Duration::days(1)).to_rfc2822();
+        let params: SendMessageParamList<String> = SendMessageParamList::default()
+            .add(SendMessageParam::OTag("one"))
+            .add(SendMessageParam::OTag("two"))
+            .add(SendMessageParam::ODeliveryTime(&delivery_time))
+            .add(SendMessageParam::CustomHeader { key: "X-Campaign", value: "spring" });
+
+        assert!(params.validate().is_ok());
+
+        let delivery_time = (Utc::now() + Duration::days(4)).to_rfc2822();
+        let params: SendMessageParamList<String> = SendMessageParamList::default()
+            .add(SendMessageParam::OTag("one"))
+            .add(SendMessageParam::OTag("two"))
+            .add(SendMessageParam::OTag("three"))
+            .add(SendMessageParam::OTag("four"))
+            .add(SendMessageParam::ODeliveryTime(&delivery_time))
+            .add(SendMessageParam::CustomHeader { key: "X Bad", value: "line\r\nbreak" })
+            .add(SendMessageParam::TVersion("v2"));
+
+        match params.validate() {
+            Err(ParamError::Validation(errors)) => assert_eq!(errors.len(), 5),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        let params: SendMessageParamList<String> = SendMessageParamList::default()
+            .add(SendMessageParam::TVersion("v2"))
+            .without_validation();
+
+        assert!(params.skip_validation);
+    }
+
+    #[test]
+    fn validate_addresses() {
+        let params: SendMessageParamList<String> = SendMessageParamList::default()
+            .add(SendMessageParam::From("not an address"))
+            .add(SendMessageParam::To("bob@example.com, alice@"))
+            .add(SendMessageParam::OTag(""));
+
+        match params.validate() {
+            Err(ParamError::Validation(errors)) => {
+                assert_eq!(errors.len(), 3);
+                assert!(matches!(&errors[0], ParamError::InvalidAddress(key, _) if key == "from"));
+                assert!(matches!(&errors[1], ParamError::InvalidAddress(key, _) if key == "to"));
+                assert!(matches!(&errors[2], ParamError::InvalidValue(key, _) if key == "o:tag"));
+            },
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        let params = params.without_validation();
+        let pairs = params.to_pairs().unwrap();
+
+        assert_eq!(pairs[0], ("from".to_string(), "not an address".to_string()));
+        assert_eq!(pairs[1], ("to".to_string(), "bob@example.com, alice@".to_string()));
+    }
 }
@@ -0,0 +1,221 @@
+//! Request and response module for managing tags and fetching their stats.
+//!
+//! Tags are created by sending messages with the `o:tag` option. Stats for a
+//! tag take the same parameters, and return the same items, as
+//! [`get_stats`](../get_stats/index.html).
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     ParamList,
+//! #     get_stats::{GetStatsParam, GetStatsParamList},
+//! #     tags::{GetTagsParam, GetTagsParamList},
+//! # };
+//! # let client = Client::new("", "");
+//! let request = GetTagsParamList::default()
+//!     .add(GetTagsParam::Limit(10));
+//!
+//! let tags = client.get_tags(request).unwrap();
+//!
+//! client.update_tag("newsletter", "Monthly newsletter").unwrap();
+//!
+//! let stats = client.get_tag_stats("newsletter", GetStatsParamList::default()).unwrap();
+//! let countries = client.get_tag_countries("newsletter").unwrap();
+//! ```
+//!
+//! [API Documentation](https://documentation.mailgun.com/en/latest/api-tags.html)
+
+use crate::get_stats::{AggregateCounts, StatItem};
+use crate::{InvalidItem, ItemList, Paging, Param, ParamError, ParamList};
+
+use std::collections::HashMap;
+
+//- Request
+
+/// A parameter for fetching tags for a domain.
+#[derive(Debug)]
+pub enum GetTagsParam {
+    /// Maximum number of records to return (default: 100).
+    Limit(usize),
+}
+
+impl Param for GetTagsParam {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Limit(v) => ("limit".to_string(), v.to_string()),
+        })
+    }
+}
+
+/// List of parameters for fetching tags for a domain.
+#[derive(Debug, Default)]
+pub struct GetTagsParamList {
+    pub values: Vec<GetTagsParam>,
+}
+
+impl ParamList for GetTagsParamList {
+    type ParamType = GetTagsParam;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+//- Response
+
+/// Response returned by get tags endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetTagsResponse {
+    pub items: Vec<Tag>,
+    pub paging: Paging,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetTagsResponse {
+    type Item = Tag;
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
+}
+
+/// A single tag.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Tag {
+    pub tag: String,
+    pub description: Option<String>,
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by update and delete tag endpoints.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TagMessageResponse {
+    pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by get tag stats endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetTagStatsResponse {
+    pub tag: String,
+    pub description: Option<String>,
+    pub start: String,
+    pub end: String,
+    pub resolution: String,
+    pub stats: Vec<StatItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by get tag countries endpoint, keyed by country code.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetTagCountriesResponse {
+    pub tag: String,
+    pub country: HashMap<String, AggregateCounts>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by get tag providers endpoint, keyed by email provider
+/// domain.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetTagProvidersResponse {
+    pub tag: String,
+    pub provider: HashMap<String, AggregateCounts>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by get tag devices endpoint, keyed by device type.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetTagDevicesResponse {
+    pub tag: String,
+    pub device: HashMap<String, AggregateCounts>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoints::decode_lenient;
+
+    #[test]
+    fn deserialize_tags() {
+        let raw = r#"{
+            "items": [
+                {
+                    "tag": "newsletter",
+                    "description": "Monthly newsletter",
+                    "first-seen": "2019-10-01T00:00:00Z",
+                    "last-seen": "2019-10-14T00:00:00Z"
+                },
+                { "tag": "receipt", "description": null },
+                { "description": "missing tag" }
+            ],
+            "paging": {
+                "first": "https://api.mailgun.net/v3/domain.com/tags?limit=100",
+                "last": "https://api.mailgun.net/v3/domain.com/tags?page=last&limit=100",
+                "next": "https://api.mailgun.net/v3/domain.com/tags?page=next&tag=receipt&limit=100",
+                "previous": "https://api.mailgun.net/v3/domain.com/tags?page=prev&tag=newsletter&limit=100"
+            }
+        }"#;
+
+        let response: GetTagsResponse = decode_lenient(raw).unwrap();
+
+        assert_eq!(response.items.len(), 2);
+        assert_eq!(response.items[0].first_seen.as_deref(), Some("2019-10-01T00:00:00Z"));
+        assert!(response.items[1].description.is_none());
+        assert_eq!(response.invalid_items.len(), 1);
+        assert_eq!(response.invalid_items[0].index, 2);
+    }
+
+    #[test]
+    fn deserialize_tag_stats() {
+        let raw = r#"{
+            "tag": "newsletter",
+            "description": "Monthly newsletter",
+            "start": "Mon, 14 Oct 2019 00:00:00 UTC",
+            "end": "Tue, 15 Oct 2019 00:00:00 UTC",
+            "resolution": "day",
+            "stats": [
+                {
+                    "time": "Mon, 14 Oct 2019 00:00:00 UTC",
+                    "accepted": { "outgoing": 10, "incoming": 0, "total": 10 },
+                    "opened": { "total": 4, "unique": 3 }
+                }
+            ]
+        }"#;
+
+        let response: GetTagStatsResponse = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(response.tag, "newsletter");
+        assert_eq!(response.stats.len(), 1);
+        assert_eq!(response.stats[0].accepted.as_ref().unwrap().total, 10);
+        assert!(response.stats[0].delivered.is_none());
+
+        let raw = r#"{
+            "tag": "newsletter",
+            "country": {
+                "us": { "accepted": 7, "delivered": 6, "opened": 3, "unique_opened": 2 }
+            }
+        }"#;
+
+        let response: GetTagCountriesResponse = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(response.country["us"].delivered, 6);
+        assert_eq!(response.country["us"].clicked, 0);
+    }
+}
@@ -0,0 +1,351 @@
+//! Request and response module for validating email addresses.
+//!
+//! Single addresses are validated immediately. Larger lists are uploaded as a
+//! CSV file to a bulk validation job, which is processed in the background
+//! and can be polled until its results are ready to download.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     ParamList,
+//! #     validations::{
+//! #         DownloadFormat,
+//! #         ValidateAddressParam,
+//! #         ValidateAddressParamList,
+//! #         ValidationResult,
+//! #     },
+//! # };
+//! # let client = Client::new("", "");
+//! let request = ValidateAddressParamList::default()
+//!     .add(ValidateAddressParam::Address("bob@domain.com"));
+//!
+//! let validation = client.validate_address(request).unwrap();
+//!
+//! if validation.result != ValidationResult::Deliverable {
+//!     println!("Did you mean {:?}?", validation.did_you_mean);
+//! }
+//!
+//! let csv = std::fs::read("signups.csv").unwrap();
+//! client.create_bulk_validation("signups", &csv).unwrap();
+//!
+//! let job = client.get_bulk_validation("signups").unwrap();
+//!
+//! if job.download_url.is_some() {
+//!     let results = client.download_bulk_validation(&job, DownloadFormat::Csv).unwrap();
+//! }
+//! ```
+//!
+//! [API Documentation](https://documentation.mailgun.com/en/latest/api-email-validation.html)
+
+use crate::{InvalidItem, ItemList, Paging, Param, ParamError, ParamList};
+
+//- Request
+
+/// A parameter for validating a single address.
+#[derive(Debug)]
+pub enum ValidateAddressParam<'a> {
+    /// The address to validate. Required.
+    Address(&'a str),
+    /// Whether to check the mailbox with the email provider (default: `true`).
+    ProviderLookup(bool),
+}
+
+impl<'a> Param for ValidateAddressParam<'a> {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Address(v) => ("address".to_string(), v.to_string()),
+            Self::ProviderLookup(v) => ("provider_lookup".to_string(), v.to_string()),
+        })
+    }
+}
+
+/// List of parameters for validating a single address.
+#[derive(Debug, Default)]
+pub struct ValidateAddressParamList<'a> {
+    pub values: Vec<ValidateAddressParam<'a>>,
+}
+
+impl<'a> ParamList for ValidateAddressParamList<'a> {
+    type ParamType = ValidateAddressParam<'a>;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+/// A parameter for fetching bulk validation jobs.
+#[derive(Debug)]
+pub enum GetBulkValidationsParam {
+    /// Maximum number of records to return (default: 500).
+    Limit(usize),
+}
+
+impl Param for GetBulkValidationsParam {
+    fn try_as_tuple(&self) -> Result<(String, String), ParamError> {
+        Ok(match self {
+            Self::Limit(v) => ("limit".to_string(), v.to_string()),
+        })
+    }
+}
+
+/// List of parameters for fetching bulk validation jobs.
+#[derive(Debug, Default)]
+pub struct GetBulkValidationsParamList {
+    pub values: Vec<GetBulkValidationsParam>,
+}
+
+impl ParamList for GetBulkValidationsParamList {
+    type ParamType = GetBulkValidationsParam;
+
+    fn add(mut self, param: Self::ParamType) -> Self {
+        self.values.push(param);
+
+        self
+    }
+}
+
+/// Format of the results of a bulk validation job.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DownloadFormat {
+    Csv,
+    Json,
+}
+
+//- Response
+
+/// Overall verdict on an address.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationResult {
+    Deliverable,
+    Undeliverable,
+    DoNotSend,
+    CatchAll,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Risk of sending to an address.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationRisk {
+    Low,
+    Medium,
+    High,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Response returned by validate address endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ValidateAddressResponse {
+    pub address: String,
+    pub did_you_mean: Option<String>,
+    pub is_disposable_address: bool,
+    pub is_role_address: bool,
+    #[serde(default)]
+    pub reason: Vec<String>,
+    pub result: ValidationResult,
+    pub risk: ValidationRisk,
+    pub root_address: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by create bulk validation endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateBulkValidationResponse {
+    pub id: String,
+    pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by get bulk validations endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetBulkValidationsResponse {
+    pub jobs: Vec<BulkValidationJob>,
+    pub paging: Paging,
+    pub total: i64,
+    #[serde(skip)]
+    pub invalid_items: Vec<InvalidItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ItemList for GetBulkValidationsResponse {
+    type Item = BulkValidationJob;
+
+    const FIELD: &'static str = "jobs";
+
+    fn invalid_items_mut(&mut self) -> &mut Vec<InvalidItem> {
+        &mut self.invalid_items
+    }
+}
+
+/// A single bulk validation job.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BulkValidationJob {
+    pub id: String,
+    pub status: String,
+    pub created_at: i64,
+    pub quantity: i64,
+    pub records_processed: i64,
+    pub download_url: Option<BulkValidationDownloadUrl>,
+    pub summary: Option<BulkValidationSummary>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Download locations found in [`BulkValidationJob`](struct.BulkValidationJob.html).
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BulkValidationDownloadUrl {
+    pub csv: String,
+    pub json: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Result counts found in [`BulkValidationJob`](struct.BulkValidationJob.html).
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BulkValidationSummary {
+    pub result: BulkValidationResultSummary,
+    pub risk: BulkValidationRiskSummary,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Counts of each result found in [`BulkValidationSummary`](struct.BulkValidationSummary.html).
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BulkValidationResultSummary {
+    pub deliverable: i64,
+    pub undeliverable: i64,
+    pub do_not_send: i64,
+    pub catch_all: i64,
+    pub unknown: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Counts of each risk found in [`BulkValidationSummary`](struct.BulkValidationSummary.html).
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BulkValidationRiskSummary {
+    pub low: i64,
+    pub medium: i64,
+    pub high: i64,
+    pub unknown: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response returned by cancel bulk validation endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CancelBulkValidationResponse {
+    pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_validate_address_response() {
+        let raw = r#"{
+            "address": "bob@domain.com",
+            "did_you_mean": null,
+            "engagement": null,
+            "is_disposable_address": false,
+            "is_role_address": false,
+            "reason": [],
+            "result": "deliverable",
+            "risk": "low",
+            "root_address": null
+        }"#;
+
+        let response: ValidateAddressResponse = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(response.result, ValidationResult::Deliverable);
+        assert_eq!(response.risk, ValidationRisk::Low);
+        assert!(response.reason.is_empty());
+        assert!(response.extra.contains_key("engagement"));
+
+        let raw = r#"{
+            "address": "bob@domain.con",
+            "did_you_mean": "bob@domain.com",
+            "is_disposable_address": false,
+            "is_role_address": false,
+            "result": "undeliverable_for_now",
+            "risk": "extreme",
+            "root_address": null
+        }"#;
+
+        let response: ValidateAddressResponse = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(response.did_you_mean.as_deref(), Some("bob@domain.com"));
+        assert_eq!(response.result, ValidationResult::Unknown);
+        assert_eq!(response.risk, ValidationRisk::Unknown);
+    }
+
+    #[test]
+    fn deserialize_bulk_validation_job() {
+        let raw = r#"{
+            "id": "signups",
+            "status": "uploaded",
+            "created_at": 1590080191,
+            "quantity": 207665,
+            "records_processed": 207665,
+            "download_url": {
+                "csv": "https://example.com/signups.csv.gz",
+                "json": "https://example.com/signups.json.gz"
+            },
+            "summary": {
+                "result": { "deliverable": 181854, "undeliverable": 5647, "do_not_send": 0, "catch_all": 2, "unknown": 5 },
+                "risk": { "low": 17, "medium": 5, "high": 3, "unknown": 0 }
+            }
+        }"#;
+
+        let job: BulkValidationJob = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(job.id, "signups");
+        assert_eq!(job.download_url.unwrap().csv, "https://example.com/signups.csv.gz");
+
+        let summary = job.summary.unwrap();
+
+        assert_eq!(summary.result.deliverable, 181854);
+        assert_eq!(summary.risk.high, 3);
+
+        let raw = r#"{
+            "id": "pending",
+            "status": "created",
+            "created_at": 1590080191,
+            "quantity": 0,
+            "records_processed": 0,
+            "download_url": null,
+            "summary": null
+        }"#;
+
+        let job: BulkValidationJob = serde_json::from_str(raw).unwrap();
+
+        assert!(job.download_url.is_none());
+        assert!(job.summary.is_none());
+    }
+
+    #[test]
+    fn deserialize_bulk_validation_responses() {
+        let raw = r#"{ "id": "signups", "message": "The validation job was submitted." }"#;
+        let response: CreateBulkValidationResponse = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(response.id, "signups");
+
+        let raw = r#"{ "message": "Validation job canceled." }"#;
+        let response: CancelBulkValidationResponse = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(response.message, "Validation job canceled.");
+    }
+}
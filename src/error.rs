@@ -0,0 +1,130 @@
+//! Errors returned by the API.
+
+use crate::response::{request_id, RateLimit};
+
+use std::fmt;
+use std::io;
+
+/// A non-2xx response from the API.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: u16,
+    /// The `message` or `error` field of the response, if it was JSON.
+    pub message: Option<String>,
+    /// Raw response body.
+    pub body: String,
+    pub method: String,
+    pub url: String,
+    pub request_id: Option<String>,
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl ApiError {
+    /// Read an error from a response.
+    pub(crate) fn from_response(method: &str, response: ureq::Response) -> Result<Self, io::Error> {
+        let status = response.status();
+        let url = response.get_url().to_string();
+        let request_id = request_id(&response);
+        let rate_limit = RateLimit::from_response(&response);
+
+        let body = response.into_string()?;
+        let message = serde_json::from_str::<ErrorResponse>(&body)
+            .ok()
+            .map(ErrorResponse::into_message);
+
+        Ok(Self {
+            status,
+            message,
+            body,
+            method: method.to_string(),
+            url,
+            request_id,
+            rate_limit,
+        })
+    }
+
+    /// Whether the same request may succeed if it is sent again later, which
+    /// is the case when rate limited or for server errors.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.status, 408 | 429 | 500 | 502 | 503 | 504)
+    }
+
+    /// Whether the request was refused because too many requests were sent.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == 429
+    }
+
+    /// Whether the API key is invalid or not allowed to make the request.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self.status, 401 | 403)
+    }
+
+    /// Whether the requested resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        self.status == 404
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} returned a {} status code", self.method, self.url, self.status)?;
+
+        match &self.message {
+            Some(message) => write!(f, ": {}", message),
+            None if !self.body.is_empty() => write!(f, ": {}", self.body),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Body of an error response.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ErrorResponse {
+    WithError {
+        #[serde(alias = "Error")]
+        error: String,
+    },
+    WithMessage {
+        message: String,
+    },
+}
+
+impl ErrorResponse {
+    fn into_message(self) -> String {
+        match self {
+            Self::WithError { error } => error,
+            Self::WithMessage { message } => message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_response() {
+        let response = "HTTP/1.1 429 Too Many Requests\r\n\
+            X-Mailgun-Request-Id: abc123\r\n\
+            X-RateLimit-Limit: 300\r\n\
+            X-RateLimit-Remaining: 0\r\n\
+            Retry-After: 30\r\n\
+            \r\n\
+            {\"message\": \"Too many requests\"}"
+            .parse::<ureq::Response>()
+            .unwrap();
+
+        let error = ApiError::from_response("GET", response).unwrap();
+
+        assert_eq!(error.status, 429);
+        assert_eq!(error.message.as_deref(), Some("Too many requests"));
+        assert_eq!(error.request_id.as_deref(), Some("abc123"));
+        assert_eq!(error.rate_limit.as_ref().and_then(|r| r.retry_after), Some(30));
+        assert!(error.is_rate_limited());
+        assert!(error.is_retryable());
+        assert!(!error.is_not_found());
+    }
+}
@@ -0,0 +1,326 @@
+//! Parser for inbound messages forwarded to an HTTP endpoint by a route.
+//!
+//! When a route forwards a message to a URL, Mailgun `POST`s the parsed
+//! message as a `multipart/form-data` body (or `x-www-form-urlencoded` when
+//! there are no attachments). This module turns that body into an
+//! [`InboundMessage`](struct.InboundMessage.html) and verifies its signature.
+//! It only needs the raw body and the `Content-Type` header, so it can be used
+//! with any web framework.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # use mailgun_sdk::inbound::InboundMessage;
+//! # let body: Vec<u8> = vec![];
+//! # let content_type = "multipart/form-data; boundary=xyz";
+//! let message = InboundMessage::parse(&body, content_type).unwrap();
+//!
+//! message.verify("webhook-signing-key").unwrap();
+//!
+//! println!("{:?}: {:?}", message.from, message.stripped_text);
+//!
+//! for attachment in message.attachments.iter() {
+//!     println!("{:?} ({} bytes)", attachment.filename, attachment.data.len());
+//! }
+//! ```
+//!
+//! [Documentation](https://documentation.mailgun.com/en/latest/user_manual.html#parsed-messages-parameters)
+
+use crate::multipart;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use thiserror::Error;
+
+use std::collections::HashMap;
+
+/// Errors that might be encountered when parsing or verifying an inbound
+/// message.
+#[derive(Debug, Error)]
+pub enum InboundError {
+    /// The body is neither `multipart/form-data` nor
+    /// `application/x-www-form-urlencoded`.
+    #[error("Unsupported content type: {0}")]
+    UnsupportedContentType(String),
+
+    /// The body could not be split into form fields.
+    #[error("Malformed form body: {0}")]
+    MalformedBody(String),
+
+    /// A field that is always sent by Mailgun is missing.
+    #[error("Missing field '{0}'")]
+    MissingField(&'static str),
+
+    /// A field that should contain JSON could not be parsed.
+    #[error("Field '{0}' contains invalid JSON: {1}")]
+    InvalidJson(&'static str, serde_json::error::Error),
+
+    /// The signature does not match the timestamp and token.
+    #[error("Signature is invalid")]
+    InvalidSignature,
+}
+
+/// A message forwarded by a route.
+#[derive(Debug)]
+pub struct InboundMessage {
+    pub recipient: String,
+    pub sender: String,
+    pub from: Option<String>,
+    pub subject: Option<String>,
+    pub body_plain: Option<String>,
+    pub stripped_text: Option<String>,
+    pub stripped_signature: Option<String>,
+    pub body_html: Option<String>,
+    pub stripped_html: Option<String>,
+    pub message_headers: Vec<(String, String)>,
+    /// Maps `Content-ID` values to the name of the attachment field.
+    pub content_id_map: HashMap<String, String>,
+    pub timestamp: String,
+    pub token: String,
+    pub signature: String,
+    pub attachments: Vec<InboundAttachment>,
+    /// Any other text fields, such as custom variables.
+    pub fields: HashMap<String, String>,
+}
+
+/// A single attachment found in [`InboundMessage`](struct.InboundMessage.html).
+#[derive(Debug)]
+pub struct InboundAttachment {
+    /// Name of the form field, such as `attachment-1`.
+    pub field: String,
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl InboundMessage {
+    /// Parse a form body posted by a route, given the value of the request's
+    /// `Content-Type` header.
+    pub fn parse(body: &[u8], content_type: &str) -> Result<Self, InboundError> {
+        let mime_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+
+        let mut fields = HashMap::new();
+        let mut attachments = vec![];
+
+        match mime_type.as_str() {
+            "multipart/form-data" => {
+                let boundary = multipart::boundary(content_type)
+                    .ok_or_else(|| InboundError::MalformedBody("content type has no boundary".to_string()))?;
+
+                for part in multipart::decode(body, &boundary).map_err(InboundError::MalformedBody)? {
+                    if part.filename.is_some() {
+                        attachments.push(InboundAttachment {
+                            field: part.name,
+                            filename: part.filename,
+                            content_type: part.content_type,
+                            data: part.data,
+                        });
+                    } else {
+                        fields.insert(part.name, String::from_utf8_lossy(&part.data).into_owned());
+                    }
+                }
+            },
+            "application/x-www-form-urlencoded" => {
+                let body = std::str::from_utf8(body)
+                    .map_err(|error| InboundError::MalformedBody(error.to_string()))?;
+
+                for pair in body.split('&').filter(|pair| !pair.is_empty()) {
+                    let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+
+                    fields.insert(decode_component(key)?, decode_component(value)?);
+                }
+            },
+            _ => return Err(InboundError::UnsupportedContentType(content_type.to_string())),
+        }
+
+        let message_headers = match fields.remove("message-headers") {
+            Some(headers) => serde_json::from_str(&headers)
+                .map_err(|error| InboundError::InvalidJson("message-headers", error))?,
+            None => vec![],
+        };
+
+        let content_id_map = match fields.remove("content-id-map") {
+            Some(map) => serde_json::from_str(&map)
+                .map_err(|error| InboundError::InvalidJson("content-id-map", error))?,
+            None => HashMap::new(),
+        };
+
+        let mut required = |name: &'static str| fields.remove(name).ok_or(InboundError::MissingField(name));
+
+        let recipient = required("recipient")?;
+        let sender = required("sender")?;
+        let timestamp = required("timestamp")?;
+        let token = required("token")?;
+        let signature = required("signature")?;
+
+        Ok(Self {
+            recipient,
+            sender,
+            from: fields.remove("from"),
+            subject: fields.remove("subject"),
+            body_plain: fields.remove("body-plain"),
+            stripped_text: fields.remove("stripped-text"),
+            stripped_signature: fields.remove("stripped-signature"),
+            body_html: fields.remove("body-html"),
+            stripped_html: fields.remove("stripped-html"),
+            message_headers,
+            content_id_map,
+            timestamp,
+            token,
+            signature,
+            attachments,
+            fields,
+        })
+    }
+
+    /// Check that the message was sent by Mailgun, using the webhook signing
+    /// key of the account.
+    ///
+    /// This does not check how old the timestamp is, or whether the token has
+    /// been seen before. Callers who need protection against replayed requests
+    /// should do so themselves.
+    pub fn verify(&self, signing_key: &str) -> Result<(), InboundError> {
+        if verify_signature(signing_key, &self.timestamp, &self.token, &self.signature) {
+            Ok(())
+        } else {
+            Err(InboundError::InvalidSignature)
+        }
+    }
+
+    /// Find the attachment referenced by a `Content-ID`, such as an inline
+    /// image.
+    pub fn attachment_for_content_id(&self, content_id: &str) -> Option<&InboundAttachment> {
+        let field = self.content_id_map.get(content_id)?;
+
+        self.attachments.iter().find(|attachment| &attachment.field == field)
+    }
+}
+
+/// Check a Mailgun signature, which is the hex encoded HMAC-SHA256 of the
+/// timestamp followed by the token, keyed with the webhook signing key.
+pub fn verify_signature(signing_key: &str, timestamp: &str, token: &str, signature: &str) -> bool {
+    let signature = match hex::decode(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+
+    let mut mac = match Hmac::<Sha256>::new_from_slice(signing_key.as_bytes()) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+
+    mac.update(timestamp.as_bytes());
+    mac.update(token.as_bytes());
+
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Decode a single `x-www-form-urlencoded` key or value.
+fn decode_component(value: &str) -> Result<String, InboundError> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let byte = bytes.get(index + 1..index + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| InboundError::MalformedBody(format!("invalid percent encoding in '{}'", value)))?;
+
+                decoded.push(byte);
+                index += 2;
+            },
+            byte => decoded.push(byte),
+        }
+
+        index += 1;
+    }
+
+    String::from_utf8(decoded).map_err(|error| InboundError::MalformedBody(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: &str = "cfc4597630d89d70b0474815032b575c2aa33c26b2e8cbb4032820b26ee19855";
+
+    #[test]
+    fn parse_multipart() {
+        let body = [
+            "--xyz",
+            "Content-Disposition: form-data; name=\"recipient\"",
+            "",
+            "support@domain.com",
+            "--xyz",
+            "Content-Disposition: form-data; name=\"sender\"",
+            "",
+            "bob@example.com",
+            "--xyz",
+            "Content-Disposition: form-data; name=\"stripped-text\"",
+            "",
+            "Hello,\r\nit is broken.",
+            "--xyz",
+            "Content-Disposition: form-data; name=\"message-headers\"",
+            "",
+            "[[\"Subject\", \"Help\"]]",
+            "--xyz",
+            "Content-Disposition: form-data; name=\"timestamp\"",
+            "",
+            "1600000000",
+            "--xyz",
+            "Content-Disposition: form-data; name=\"token\"",
+            "",
+            "abc123token",
+            "--xyz",
+            "Content-Disposition: form-data; name=\"signature\"",
+            "",
+            SIGNATURE,
+            "--xyz",
+            "Content-Disposition: form-data; name=\"attachment-1\"; filename=\"log; 1.txt\"",
+            "Content-Type: text/plain",
+            "",
+            "line one\r\nline two",
+            "--xyz--",
+            "",
+        ].join("\r\n");
+
+        let message = InboundMessage::parse(body.as_bytes(), "multipart/form-data; boundary=\"xyz\"").unwrap();
+
+        assert_eq!(message.recipient, "support@domain.com");
+        assert_eq!(message.sender, "bob@example.com");
+        assert_eq!(message.stripped_text.as_deref(), Some("Hello,\r\nit is broken."));
+        assert_eq!(message.message_headers, vec![("Subject".to_string(), "Help".to_string())]);
+        assert_eq!(message.attachments.len(), 1);
+        assert_eq!(message.attachments[0].filename.as_deref(), Some("log; 1.txt"));
+        assert_eq!(message.attachments[0].content_type.as_deref(), Some("text/plain"));
+        assert_eq!(message.attachments[0].data, b"line one\r\nline two");
+
+        assert!(message.verify("key-secret").is_ok());
+        assert!(message.verify("key-other").is_err());
+    }
+
+    #[test]
+    fn parse_urlencoded() {
+        let body = format!(
+            "recipient=support%40domain.com&sender=bob%40example.com&subject=Need+help&timestamp=1600000000&token=abc123token&signature={}&my-var=1",
+            SIGNATURE,
+        );
+
+        let message = InboundMessage::parse(body.as_bytes(), "application/x-www-form-urlencoded").unwrap();
+
+        assert_eq!(message.recipient, "support@domain.com");
+        assert_eq!(message.subject.as_deref(), Some("Need help"));
+        assert_eq!(message.fields.get("my-var").map(String::as_str), Some("1"));
+        assert!(message.verify("key-secret").is_ok());
+    }
+
+    #[test]
+    fn parse_missing_field() {
+        let result = InboundMessage::parse(b"recipient=a%40b.com", "application/x-www-form-urlencoded");
+
+        assert!(matches!(result, Err(InboundError::MissingField("sender"))));
+    }
+}
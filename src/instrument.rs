@@ -0,0 +1,100 @@
+//! Instrumentation of requests made by the client.
+//!
+//! With the `tracing` feature, each call opens a `mailgun.request` span with
+//! the endpoint, domain, HTTP status, duration and request ID. The API key,
+//! URLs and request or response bodies are never recorded, as messages are
+//! sent as request parameters.
+//!
+//! With the `metrics` feature, calls, their durations and accepted messages
+//! are recorded through the `metrics` facade.
+
+#![cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+
+use crate::client::ClientError;
+use crate::request::ApiRequest;
+use crate::response::ResponseMeta;
+
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+use std::time::Instant;
+
+/// Endpoints that send a message.
+#[cfg(feature = "metrics")]
+const SEND_ENDPOINTS: &[&str] = &["send_message", "send_mime", "resend_stored_message"];
+
+/// A single call to an endpoint.
+pub(crate) struct Call {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "metrics")]
+    endpoint: &'static str,
+    #[cfg(any(feature = "metrics", feature = "tracing"))]
+    started: Instant,
+}
+
+impl Call {
+    pub fn start(request: &ApiRequest, domain: &str) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "mailgun.request",
+                endpoint = request.endpoint,
+                method = request.method.as_str(),
+                domain,
+                status = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            ).entered(),
+            #[cfg(feature = "metrics")]
+            endpoint: request.endpoint,
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
+            started: Instant::now(),
+        }
+    }
+
+    pub fn finish(&self, result: Result<&ResponseMeta, &ClientError>) {
+        #[cfg(feature = "metrics")]
+        {
+            let status = match result {
+                Ok(meta) => meta.status.to_string(),
+                Err(error) => error.api_error().map_or_else(|| "error".to_string(), |error| error.status.to_string()),
+            };
+
+            metrics::counter!("mailgun_requests_total", "endpoint" => self.endpoint, "status" => status).increment(1);
+            metrics::histogram!("mailgun_request_duration_seconds", "endpoint" => self.endpoint)
+                .record(self.started.elapsed().as_secs_f64());
+
+            if result.is_ok() && SEND_ENDPOINTS.contains(&self.endpoint) {
+                metrics::counter!("mailgun_messages_accepted_total", "endpoint" => self.endpoint).increment(1);
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            let (status, request_id) = match result {
+                Ok(meta) => (Some(meta.status), meta.request_id.as_deref()),
+                Err(error) => match error.api_error() {
+                    Some(error) => (Some(error.status), error.request_id.as_deref()),
+                    None => (None, None),
+                },
+            };
+
+            if let Some(status) = status {
+                self.span.record("status", status);
+            }
+
+            if let Some(request_id) = request_id {
+                self.span.record("request_id", request_id);
+            }
+
+            self.span.record("duration_ms", self.started.elapsed().as_millis() as u64);
+
+            if let Err(error) = result {
+                if error.is_rate_limited() {
+                    tracing::warn!("rate limited");
+                } else {
+                    tracing::debug!(?status, "request failed");
+                }
+            }
+        }
+    }
+}
@@ -30,8 +30,9 @@
 //! }
 //! ```
 //!
-//! This crate does not enforce rules on sending messages. However, you should
-//! almost always set the following when sending a message:
+//! Message parameters are validated against the API's documented limits before
+//! sending, but no parameter is required. However, you should almost always set
+//! the following when sending a message:
 //!
 //! - **Subject**
 //! - **To**
@@ -42,6 +43,57 @@
 //! you notice any that do not work, please feel free to create a ticket, or
 //! create a pull a request.
 //!
+//! ### API Key
+//!
+//! The API key is kept in an [`ApiKey`](struct.ApiKey.html), which is never
+//! shown when logging a client. It can be loaded from the `MAILGUN_API_KEY`
+//! environment variable, or a file named by `MAILGUN_API_KEY_FILE`, and be
+//! replaced while the client is in use.
+//!
+//! ```no_run
+//! # use mailgun_sdk::{ApiKey, Client};
+//! let client = Client::with_api_key(ApiKey::from_env().unwrap(), "Domain");
+//!
+//! // Later, once the key has been rotated.
+//! client.rotate_api_key(ApiKey::from_env().unwrap());
+//! ```
+//!
+//! ### Configuration
+//!
+//! `Client::new` uses default timeouts and a connection pool of its own. Use
+//! `Client::builder` to change the timeouts, share a pool between clients, or
+//! go through a proxy.
+//!
+//! ```no_run
+//! # use mailgun_sdk::Client;
+//! # use std::time::Duration;
+//! let client = Client::builder("ApiKey", "Domain")
+//!     .connect_timeout(Duration::from_secs(5))
+//!     .read_timeout(Duration::from_secs(20))
+//!     .proxy("user:password@proxy.internal:8080")
+//!     .user_agent("my-app/1.0")
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! ### Tracing
+//!
+//! With the `tracing` feature enabled, every API call opens a `mailgun.request`
+//! span holding the endpoint, domain, HTTP status, duration and request ID,
+//! and rate limiting is logged as an event. The API key and message contents
+//! are never recorded.
+//!
+//! ### Metrics
+//!
+//! With the `metrics` feature enabled, API calls are recorded through the
+//! [`metrics`](https://docs.rs/metrics) facade, for any installed exporter:
+//!
+//! - `mailgun_requests_total`: calls, by `endpoint` and `status`. The status
+//!   is `error` when no response was received.
+//! - `mailgun_request_duration_seconds`: duration of calls, by `endpoint`.
+//! - `mailgun_messages_accepted_total`: messages accepted for delivery, by
+//!   `endpoint`.
+//!
 //! ### Pagination
 //!
 //! For API calls that return a list of results, MailGun returns a `paging`
@@ -70,6 +122,47 @@
 //! }
 //! ```
 //!
+//! ### Response Metadata
+//!
+//! Endpoints only return the parsed response body. Wrap any call in
+//! `with_meta` to also get the response's status, headers, request ID, rate
+//! limit and timing.
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     send_message::SendMessageParamList,
+//! # };
+//! # let client = Client::new("ApiKey", "Domain");
+//! let response = client.with_meta(|client| client.send_message(SendMessageParamList::default())).unwrap();
+//!
+//! println!("Queued {} (request {:?})", response.body.id, response.meta.request_id);
+//!
+//! if let Some(rate_limit) = response.meta.rate_limit {
+//!     println!("{:?} requests left", rate_limit.remaining);
+//! }
+//! ```
+//!
+//! ### Raw Responses
+//!
+//! Every endpoint has a `_raw` variant returning the response body as
+//! `serde_json::Value`, for reading fields that are not modeled yet. Endpoints
+//! that are not covered by this crate can be called with `request`.
+//!
+//! ```no_run
+//! # use mailgun_sdk::{
+//! #     Client,
+//! #     Method,
+//! #     get_events::GetEventsParamList,
+//! # };
+//! # let client = Client::new("ApiKey", "Domain");
+//! let events = client.get_events_raw(GetEventsParamList::default()).unwrap();
+//!
+//! println!("{}", events["items"][0]["event"]);
+//!
+//! let tracking: serde_json::Value = client.request(Method::Get, "tracking", &[]).unwrap();
+//! ```
+//!
 //! ### Further Examples
 //!
 //! ```no_run
@@ -93,17 +186,47 @@
 
 #[macro_use] extern crate serde;
 
+mod address;
+pub use address::{Address, AddressError, Mailbox, MailboxList};
+
+pub mod analytics;
+pub mod bounce;
+
 mod client;
-pub use client::{Client, ClientError};
+pub use client::{BuildError, Client, ClientBuilder, ClientError};
 
 mod endpoints;
 pub use endpoints::*;
 
+mod error;
+pub use error::ApiError;
+
+pub mod inbound;
+
+mod instrument;
+
+mod middleware;
+pub use middleware::{Middleware, Request};
+
+mod multipart;
+
 mod param;
 pub use param::*;
 
+mod request;
+pub use request::Method;
+
+mod response;
+pub use response::{RateLimit, Response, ResponseMeta};
+
+mod secret;
+pub use secret::{ApiKey, ApiKeyError, API_KEY_FILE_VAR, API_KEY_VAR};
+
 /// Base URL for the MailGun API.
-pub const MAILGUN_API_BASE: &'static str = "https://api.mailgun.net/v3";
+pub const MAILGUN_API_BASE: &str = "https://api.mailgun.net/v3";
+
+/// Base URL for version 4 of the MailGun API, used by email validation.
+pub const MAILGUN_API_V4_BASE: &str = "https://api.mailgun.net/v4";
 
 #[cfg(test)]
 pub mod test_util {
@@ -0,0 +1,153 @@
+//! Hooks run by the client around every request.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! # use mailgun_sdk::{Client, ClientError, Middleware, Request, ResponseMeta};
+//! struct Correlation;
+//!
+//! impl Middleware for Correlation {
+//!     fn before_request(&self, request: &mut Request) {
+//!         request.set_header("X-Correlation-Id", "abc123");
+//!     }
+//!
+//!     fn after_response(&self, request: &Request, meta: &ResponseMeta) {
+//!         println!("{} {} took {:?}", request.endpoint(), meta.status, meta.elapsed);
+//!     }
+//!
+//!     fn on_error(&self, request: &Request, error: &ClientError) {
+//!         eprintln!("{} failed: {}", request.endpoint(), error);
+//!     }
+//! }
+//!
+//! let client = Client::new("", "").middleware(Correlation);
+//! ```
+
+use crate::client::ClientError;
+use crate::request::ApiRequest;
+use crate::response::ResponseMeta;
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Hooks run around every request made by a client. All of them do nothing by
+/// default.
+pub trait Middleware: Send + Sync {
+    /// Called before a request is sent, in the order the middleware was
+    /// added.
+    fn before_request(&self, _request: &mut Request) {}
+
+    /// Called once a successful response has been read.
+    fn after_response(&self, _request: &Request, _meta: &ResponseMeta) {}
+
+    /// Called when a request fails, whether it could not be sent, the API
+    /// returned an error or the response could not be read.
+    fn on_error(&self, _request: &Request, _error: &ClientError) {}
+}
+
+/// A request made by the client, as seen by middleware.
+///
+/// The API key is added when the request is sent, so it is never part of a
+/// `Request`, and neither is the body.
+#[derive(Debug)]
+pub struct Request(pub(crate) ApiRequest);
+
+impl Request {
+    /// Name of the client method making the request, such as `get_events`.
+    pub fn endpoint(&self) -> &str {
+        self.0.endpoint
+    }
+
+    pub fn method(&self) -> &str {
+        self.0.method.as_str()
+    }
+
+    pub fn url(&self) -> &str {
+        &self.0.url
+    }
+
+    pub fn query(&self) -> &[(String, String)] {
+        &self.0.query
+    }
+
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.0.headers
+    }
+
+    /// Set a header, replacing any header with the same name.
+    pub fn set_header(&mut self, key: &str, value: &str) {
+        self.0.headers.retain(|(name, _)| !name.eq_ignore_ascii_case(key));
+        self.0.headers.push((key.to_string(), value.to_string()));
+    }
+}
+
+/// Middleware added to a client.
+#[derive(Clone, Default)]
+pub(crate) struct MiddlewareChain(Vec<Arc<dyn Middleware>>);
+
+impl MiddlewareChain {
+    pub fn push<M: Middleware + 'static>(&mut self, middleware: M) {
+        self.0.push(Arc::new(middleware));
+    }
+
+    pub fn before_request(&self, request: &mut Request) {
+        self.0.iter().for_each(|middleware| middleware.before_request(request));
+    }
+
+    pub fn after_response(&self, request: &Request, meta: &ResponseMeta) {
+        self.0.iter().for_each(|middleware| middleware.after_response(request, meta));
+    }
+
+    pub fn on_error(&self, request: &Request, error: &ClientError) {
+        self.0.iter().for_each(|middleware| middleware.on_error(request, error));
+    }
+}
+
+impl fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} middleware", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Middleware for Recorder {
+        fn before_request(&self, request: &mut Request) {
+            request.set_header("X-Correlation-Id", "abc123");
+
+            self.0.lock().unwrap().push(format!("before {}", request.endpoint()));
+        }
+
+        fn after_response(&self, request: &Request, _meta: &ResponseMeta) {
+            self.0.lock().unwrap().push(format!("after {}", request.endpoint()));
+        }
+
+        fn on_error(&self, request: &Request, _error: &ClientError) {
+            let header = request.headers().iter().find(|(key, _)| key == "X-Correlation-Id");
+
+            self.0.lock().unwrap().push(format!("error {} {:?}", request.endpoint(), header.map(|(_, value)| value)));
+        }
+    }
+
+    #[test]
+    fn hooks() {
+        let recorder = Recorder::default();
+        let log = recorder.0.clone();
+        let client = Client::new("key", "domain.com").middleware(recorder);
+
+        assert!(client.call::<serde_json::Value>("unknown://domain.com").is_err());
+
+        assert_eq!(*log.lock().unwrap(), vec![
+            "before call".to_string(),
+            "error call Some(\"abc123\")".to_string(),
+        ]);
+    }
+}
@@ -0,0 +1,230 @@
+//! Minimal `multipart/form-data` encoding for endpoints that upload files, and
+//! decoding for payloads posted by Mailgun.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static BOUNDARY_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A `multipart/form-data` request body.
+#[derive(Debug)]
+pub(crate) struct Multipart {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Multipart {
+    /// Create an empty form with a unique boundary.
+    pub fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        let count = BOUNDARY_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            boundary: format!("mailgun-sdk-{:x}-{:x}", nanos, count),
+            body: vec![],
+        }
+    }
+
+    /// Add a plain text field.
+    pub fn text(&mut self, name: &str, value: &str) -> &mut Self {
+        self.open_part(&format!("form-data; name=\"{}\"", escape(name)), None);
+        self.body.extend_from_slice(value.as_bytes());
+        self.body.extend_from_slice(b"\r\n");
+
+        self
+    }
+
+    /// Add a file field.
+    pub fn file(&mut self, name: &str, filename: &str, content_type: &str, data: &[u8]) -> &mut Self {
+        let disposition = format!("form-data; name=\"{}\"; filename=\"{}\"", escape(name), escape(filename));
+
+        self.open_part(&disposition, Some(content_type));
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+
+        self
+    }
+
+    /// Value to use for the request's `Content-Type` header.
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Close the form and return the encoded body.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+
+        self.body
+    }
+
+    fn open_part(&mut self, disposition: &str, content_type: Option<&str>) {
+        self.body.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+        self.body.extend_from_slice(format!("Content-Disposition: {}\r\n", disposition).as_bytes());
+
+        if let Some(content_type) = content_type {
+            self.body.extend_from_slice(format!("Content-Type: {}\r\n", content_type).as_bytes());
+        }
+
+        self.body.extend_from_slice(b"\r\n");
+    }
+}
+
+/// Escape a value for use inside a quoted header parameter.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace(['\r', '\n'], " ")
+}
+
+/// A single decoded part of a `multipart/form-data` body.
+#[derive(Debug)]
+pub(crate) struct Part {
+    pub name: String,
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+/// Find the `boundary` parameter of a `multipart/form-data` content type.
+pub(crate) fn boundary(content_type: &str) -> Option<String> {
+    header_params(content_type)
+        .into_iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+/// Split a `multipart/form-data` body into its parts.
+pub(crate) fn decode(body: &[u8], boundary: &str) -> Result<Vec<Part>, String> {
+    let delimiter = format!("--{}", boundary).into_bytes();
+    let mut parts = vec![];
+
+    let mut pos = find(body, &delimiter, 0)
+        .ok_or_else(|| "body does not contain the boundary".to_string())?;
+
+    loop {
+        pos += delimiter.len();
+
+        if body[pos..].starts_with(b"--") {
+            return Ok(parts);
+        }
+
+        pos = find(body, b"\r\n", pos).ok_or_else(|| "boundary is not followed by a line break".to_string())? + 2;
+
+        let headers_end = find(body, b"\r\n\r\n", pos)
+            .ok_or_else(|| "part headers are not terminated".to_string())?;
+        let headers = String::from_utf8_lossy(&body[pos..headers_end]);
+
+        let data_start = headers_end + 4;
+        let mut next_delimiter = b"\r\n".to_vec();
+        next_delimiter.extend_from_slice(&delimiter);
+
+        let data_end = find(body, &next_delimiter, data_start)
+            .ok_or_else(|| "part is not terminated by a boundary".to_string())?;
+
+        let mut name = None;
+        let mut filename = None;
+        let mut content_type = None;
+
+        for line in headers.split("\r\n") {
+            let (key, value) = match line.split_once(':') {
+                Some(header) => header,
+                None => continue,
+            };
+
+            if key.trim().eq_ignore_ascii_case("content-disposition") {
+                for (key, value) in header_params(value) {
+                    if key.eq_ignore_ascii_case("name") {
+                        name = Some(value);
+                    } else if key.eq_ignore_ascii_case("filename") {
+                        filename = Some(value);
+                    }
+                }
+            } else if key.trim().eq_ignore_ascii_case("content-type") {
+                content_type = Some(value.trim().to_string());
+            }
+        }
+
+        parts.push(Part {
+            name: name.ok_or_else(|| "part has no name".to_string())?,
+            filename,
+            content_type,
+            data: body[data_start..data_end].to_vec(),
+        });
+
+        pos = data_end + 2;
+    }
+}
+
+/// Parse the `key=value` parameters following the first value of a header,
+/// such as `form-data; name="field"`.
+fn header_params(value: &str) -> Vec<(String, String)> {
+    let mut params = vec![];
+    let mut chars = value.chars().peekable();
+
+    // Skip the main value.
+    for c in chars.by_ref() {
+        if c == ';' {
+            break;
+        }
+    }
+
+    loop {
+        let mut key = String::new();
+
+        for c in chars.by_ref() {
+            if c == '=' {
+                break;
+            }
+
+            key.push(c);
+        }
+
+        if key.trim().is_empty() {
+            return params;
+        }
+
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let mut value = String::new();
+
+        if chars.next_if_eq(&'"').is_some() {
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => value.extend(chars.next()),
+                    c => value.push(c),
+                }
+            }
+
+            // Skip to the next parameter.
+            for c in chars.by_ref() {
+                if c == ';' {
+                    break;
+                }
+            }
+        } else {
+            for c in chars.by_ref() {
+                if c == ';' {
+                    break;
+                }
+
+                value.push(c);
+            }
+        }
+
+        params.push((key.trim().to_string(), value.trim().to_string()));
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    if from > haystack.len() {
+        return None;
+    }
+
+    haystack[from..]
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|index| index + from)
+}
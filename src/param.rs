@@ -1,3 +1,4 @@
+use crate::AddressError;
 use thiserror::Error;
 
 /// Trait for a single request parameter.
@@ -15,9 +16,44 @@ pub trait Param {
 /// `Param` implementors.
 #[derive(Debug, Error)]
 pub enum ParamError {
+    /// A parameter contains an email address that could not be parsed.
+    #[error("Parameter '{0}' contains an invalid address")]
+    InvalidAddress(String, #[source] AddressError),
+
     /// A parameter could not be parsed into a JSON string.
-    #[error("Parameter '{0}' contains invalid JSON: {1}")]
-    InvalidJson(String, serde_json::error::Error),
+    #[error("Parameter '{0}' contains invalid JSON")]
+    InvalidJson(String, #[source] serde_json::error::Error),
+
+    /// A parameter value is outside of what the API accepts.
+    #[error("Parameter '{0}' has an invalid value: {1}")]
+    InvalidValue(String, String),
+
+    /// A parameter is not accepted by the endpoint it was given to.
+    #[error("Parameter '{0}' is not supported by this endpoint")]
+    Unsupported(String),
+
+    /// A parameter list broke one or more of the API's rules.
+    #[error("Parameters are invalid: {}", join_errors(.0))]
+    Validation(Vec<ParamError>),
+}
+
+/// Join the messages of `errors`, each followed by the messages of its
+/// sources, as the errors themselves are not part of the source chain.
+fn join_errors(errors: &[ParamError]) -> String {
+    errors.iter()
+        .map(|error| {
+            let mut message = error.to_string();
+            let mut source = std::error::Error::source(error);
+
+            while let Some(error) = source {
+                message.push_str(&format!(": {}", error));
+                source = error.source();
+            }
+
+            message
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 /// Trait for a list of request parameters.
@@ -29,3 +65,22 @@ pub trait ParamList {
     /// Add a new `Param` to the parameter list.
     fn add(self, param: Self::ParamType) -> Self;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = "bob@".parse::<crate::Mailbox>().unwrap_err();
+        let source = error.to_string();
+        let error = ParamError::InvalidAddress("to".to_string(), error);
+
+        assert_eq!(error.to_string(), "Parameter 'to' contains an invalid address");
+        assert_eq!(std::error::Error::source(&error).unwrap().to_string(), source);
+
+        let error = ParamError::Validation(vec![error]);
+
+        assert_eq!(error.to_string(), format!("Parameters are invalid: Parameter 'to' contains an invalid address: {}", source));
+    }
+}
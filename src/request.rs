@@ -0,0 +1,132 @@
+//! Description of a request to the API, sent by the client.
+
+use crate::multipart::Multipart;
+use crate::param::{Param, ParamError};
+
+/// HTTP method of a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    /// Name of the method as sent in the request line, such as `GET`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Delete => "DELETE",
+        }
+    }
+}
+
+/// Body of a request.
+#[derive(Debug)]
+pub(crate) enum Body {
+    Empty,
+    /// An `application/x-www-form-urlencoded` body.
+    Form(Vec<(String, String)>),
+    Bytes { content_type: String, data: Vec<u8> },
+}
+
+/// A request to the API, built by an endpoint method.
+#[derive(Debug)]
+pub(crate) struct ApiRequest {
+    /// Name of the client method making the request.
+    pub endpoint: &'static str,
+    pub method: Method,
+    pub url: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub body: Body,
+    /// Whether to send the API key. Pre-signed URLs do not need it.
+    pub authenticated: bool,
+}
+
+impl ApiRequest {
+    pub fn new(method: Method, url: String) -> Self {
+        Self {
+            endpoint: "",
+            method,
+            url,
+            query: vec![],
+            headers: vec![],
+            body: Body::Empty,
+            authenticated: true,
+        }
+    }
+
+    pub fn get(url: String) -> Self {
+        Self::new(Method::Get, url)
+    }
+
+    pub fn post(url: String) -> Self {
+        Self::new(Method::Post, url)
+    }
+
+    pub fn put(url: String) -> Self {
+        Self::new(Method::Put, url)
+    }
+
+    pub fn delete(url: String) -> Self {
+        Self::new(Method::Delete, url)
+    }
+
+    pub fn endpoint(mut self, endpoint: &'static str) -> Self {
+        self.endpoint = endpoint;
+
+        self
+    }
+
+    /// Add parameters to the query string.
+    pub fn query<P: Param>(mut self, params: &[P]) -> Result<Self, ParamError> {
+        for param in params {
+            self.query.push(param.try_as_tuple()?);
+        }
+
+        Ok(self)
+    }
+
+    /// Send parameters as a form body.
+    pub fn form<P: Param>(self, params: &[P]) -> Result<Self, ParamError> {
+        let pairs = params.iter()
+            .map(|param| param.try_as_tuple())
+            .collect::<Result<_, _>>()?;
+
+        Ok(self.form_pairs(pairs))
+    }
+
+    /// Send key-value pairs as a form body.
+    pub fn form_pairs(mut self, pairs: Vec<(String, String)>) -> Self {
+        self.body = Body::Form(pairs);
+
+        self
+    }
+
+    /// Send a `multipart/form-data` body.
+    pub fn multipart(mut self, form: Multipart) -> Self {
+        self.body = Body::Bytes {
+            content_type: form.content_type(),
+            data: form.finish(),
+        };
+
+        self
+    }
+
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.headers.push((key.to_string(), value.to_string()));
+
+        self
+    }
+
+    /// Do not send the API key.
+    pub fn unauthenticated(mut self) -> Self {
+        self.authenticated = false;
+
+        self
+    }
+}
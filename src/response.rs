@@ -0,0 +1,165 @@
+//! Metadata of responses returned by the API.
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+/// Response headers holding the ID of a request.
+const REQUEST_ID_HEADERS: &[&str] = &["X-Mailgun-Request-Id", "X-Request-Id"];
+
+thread_local! {
+    /// Metadata of the last response received on this thread, while inside
+    /// `Client::with_meta`. The outer `None` means nothing is being captured.
+    static CAPTURED: RefCell<Option<Option<ResponseMeta>>> = const { RefCell::new(None) };
+}
+
+/// A parsed response body, along with the response's metadata.
+#[derive(Debug)]
+pub struct Response<T> {
+    pub body: T,
+    pub meta: ResponseMeta,
+}
+
+/// Status, headers and timing of a response.
+#[derive(Clone, Debug)]
+pub struct ResponseMeta {
+    pub status: u16,
+    pub method: String,
+    pub url: String,
+    /// All response headers, in the order they were received.
+    pub headers: Vec<(String, String)>,
+    /// ID to give to Mailgun support when asking about a request.
+    pub request_id: Option<String>,
+    pub rate_limit: Option<RateLimit>,
+    /// Time from sending the request until the response headers were
+    /// received.
+    pub elapsed: Duration,
+}
+
+impl ResponseMeta {
+    pub(crate) fn from_response(method: &str, response: &ureq::Response, elapsed: Duration) -> Self {
+        let headers = response.headers_names()
+            .into_iter()
+            .flat_map(|name| {
+                response.all(&name)
+                    .into_iter()
+                    .map(|value| (name.clone(), value.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Self {
+            status: response.status(),
+            method: method.to_string(),
+            url: response.get_url().to_string(),
+            headers,
+            request_id: request_id(response),
+            rate_limit: RateLimit::from_response(response),
+            elapsed,
+        }
+    }
+
+    /// Find the value of a header, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Rate limit headers of a response.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Number of requests allowed in the current window.
+    pub limit: Option<u64>,
+    /// Number of requests left in the current window.
+    pub remaining: Option<u64>,
+    /// When the current window ends, as given by the API.
+    pub reset: Option<u64>,
+    /// Number of seconds to wait before sending another request.
+    pub retry_after: Option<u64>,
+}
+
+impl RateLimit {
+    /// Read the rate limit headers of a response. Returns `None` if there are
+    /// none.
+    pub(crate) fn from_response(response: &ureq::Response) -> Option<Self> {
+        let header = |name: &str| response.header(name).and_then(|value| value.trim().parse().ok());
+
+        let rate_limit = Self {
+            limit: header("X-RateLimit-Limit"),
+            remaining: header("X-RateLimit-Remaining"),
+            reset: header("X-RateLimit-Reset"),
+            retry_after: header("Retry-After"),
+        };
+
+        if rate_limit == Self::default() {
+            None
+        } else {
+            Some(rate_limit)
+        }
+    }
+}
+
+/// Read the request ID of a response.
+pub(crate) fn request_id(response: &ureq::Response) -> Option<String> {
+    REQUEST_ID_HEADERS.iter()
+        .find_map(|name| response.header(name))
+        .map(|value| value.to_string())
+}
+
+/// Whether response metadata is being captured on this thread.
+pub(crate) fn is_capturing() -> bool {
+    CAPTURED.with(|captured| captured.borrow().is_some())
+}
+
+/// Keep the metadata of a response, if it is being captured.
+pub(crate) fn record(meta: ResponseMeta) {
+    CAPTURED.with(|captured| {
+        if let Some(last) = captured.borrow_mut().as_mut() {
+            *last = Some(meta);
+        }
+    });
+}
+
+/// Capture the metadata of the last response received while running `call`.
+pub(crate) fn capture<T, F: FnOnce() -> T>(call: F) -> (T, Option<ResponseMeta>) {
+    let outer = CAPTURED.with(|captured| captured.replace(Some(None)));
+    let result = call();
+    let meta = CAPTURED.with(|captured| captured.replace(outer)).flatten();
+
+    (result, meta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_meta() {
+        let response = "HTTP/1.1 200 OK\r\n\
+            X-Request-Id: abc123\r\n\
+            X-RateLimit-Remaining: 299\r\n\
+            Content-Type: application/json\r\n\
+            \r\n\
+            {}"
+            .parse::<ureq::Response>()
+            .unwrap();
+
+        assert!(!is_capturing());
+
+        let ((), meta) = capture(|| {
+            assert!(is_capturing());
+
+            record(ResponseMeta::from_response("POST", &response, Duration::from_millis(20)));
+        });
+
+        let meta = meta.unwrap();
+
+        assert!(!is_capturing());
+        assert_eq!(meta.status, 200);
+        assert_eq!(meta.request_id.as_deref(), Some("abc123"));
+        assert_eq!(meta.rate_limit.as_ref().and_then(|r| r.remaining), Some(299));
+        assert_eq!(meta.header("content-type"), Some("application/json"));
+        assert_eq!(meta.elapsed, Duration::from_millis(20));
+    }
+}
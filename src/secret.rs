@@ -0,0 +1,155 @@
+//! API key of a client, kept out of logs and memory dumps.
+
+use thiserror::Error;
+use zeroize::Zeroize;
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the API key.
+pub const API_KEY_VAR: &str = "MAILGUN_API_KEY";
+
+/// Environment variable holding the path of a file containing the API key.
+pub const API_KEY_FILE_VAR: &str = "MAILGUN_API_KEY_FILE";
+
+/// A Mailgun API key.
+///
+/// The key is never shown by `Debug` or `Display`, and is overwritten with
+/// zeros when dropped.
+#[derive(Clone)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    pub fn new(key: &str) -> Self {
+        Self(key.to_string())
+    }
+
+    /// Load the key from the `MAILGUN_API_KEY` environment variable or, if it
+    /// is not set, from the file named by `MAILGUN_API_KEY_FILE`.
+    pub fn from_env() -> Result<Self, ApiKeyError> {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    /// Load the key from a file, ignoring surrounding whitespace.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ApiKeyError> {
+        let path = path.as_ref();
+        let mut contents = fs::read_to_string(path).map_err(|error| ApiKeyError::ReadFile(path.to_path_buf(), error))?;
+        let key = Self::new(contents.trim());
+
+        contents.zeroize();
+
+        if key.0.is_empty() {
+            return Err(ApiKeyError::Empty);
+        }
+
+        Ok(key)
+    }
+
+    fn from_vars<F: Fn(&str) -> Option<String>>(var: F) -> Result<Self, ApiKeyError> {
+        if let Some(mut value) = var(API_KEY_VAR) {
+            let key = Self::new(value.trim());
+
+            value.zeroize();
+
+            return if key.0.is_empty() {
+                Err(ApiKeyError::Empty)
+            } else {
+                Ok(key)
+            };
+        }
+
+        match var(API_KEY_FILE_VAR) {
+            Some(path) => Self::from_file(path),
+            None => Err(ApiKeyError::Missing),
+        }
+    }
+
+    /// The key itself, to be sent with a request.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for ApiKey {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for ApiKey {
+    fn from(key: String) -> Self {
+        Self(key)
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ApiKey(***)")
+    }
+}
+
+impl fmt::Display for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "***")
+    }
+}
+
+impl Drop for ApiKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Errors that might be encountered when loading an API key.
+#[derive(Debug, Error)]
+pub enum ApiKeyError {
+    #[error("Neither {} nor {} is set", API_KEY_VAR, API_KEY_FILE_VAR)]
+    Missing,
+
+    #[error("The API key is empty")]
+    Empty,
+
+    #[error("Could not read the API key from '{}'", .0.display())]
+    ReadFile(PathBuf, #[source] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted() {
+        let key = ApiKey::new("key-123");
+
+        assert_eq!(format!("{:?}", key), "ApiKey(***)");
+        assert_eq!(key.to_string(), "***");
+        assert_eq!(key.expose(), "key-123");
+    }
+
+    #[test]
+    fn from_vars() {
+        let path = env::temp_dir().join(format!("mailgun-sdk-api-key-{}", std::process::id()));
+        fs::write(&path, "key-from-file\n").unwrap();
+
+        let key = ApiKey::from_vars(|name| match name {
+            API_KEY_VAR => Some(" key-from-var ".to_string()),
+            _ => None,
+        });
+        assert_eq!(key.unwrap().expose(), "key-from-var");
+
+        let key = ApiKey::from_vars(|name| match name {
+            API_KEY_FILE_VAR => Some(path.display().to_string()),
+            _ => None,
+        });
+        assert_eq!(key.unwrap().expose(), "key-from-file");
+
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(ApiKey::from_vars(|_| None), Err(ApiKeyError::Missing)));
+        assert!(matches!(ApiKey::from_vars(|_| Some(String::new())), Err(ApiKeyError::Empty)));
+        assert!(matches!(ApiKey::from_file(&path), Err(ApiKeyError::ReadFile(..))));
+    }
+}